@@ -0,0 +1,232 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::position::Position;
+
+/// A rectangular grid of cells, stored row by row in a flat vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,  // Number of columns
+    height: usize, // Number of rows
+    cells: Vec<T>, // Cells in row-major order
+}
+
+/// Error returned when the rows of a grid do not all have the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedRowError {
+    pub row: usize,      // Index of the offending row
+    pub expected: usize, // Width of the first row
+    pub found: usize,    // Width of the offending row
+}
+
+impl fmt::Display for RaggedRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expected {}",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+impl Error for RaggedRowError {}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows. All rows must have the same length.
+    pub fn from_rows<I, R>(rows: I) -> Result<Self, RaggedRowError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (row, line) in rows.into_iter().enumerate() {
+            let before = cells.len();
+            cells.extend(line);
+            let found = cells.len() - before;
+
+            if row == 0 {
+                width = found;
+            } else if found != width {
+                return Err(RaggedRowError { row, expected: width, found });
+            }
+            height += 1;
+        }
+
+        Ok(Grid { width, height, cells })
+    }
+
+    /// Parses a grid from text, converting every character with `cell`.
+    ///
+    /// Empty lines (such as a trailing newline) are ignored.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, RaggedRowError>
+    where
+        F: FnMut(char) -> T,
+    {
+        Grid::from_rows(
+            input
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| line.chars().map(&mut cell).collect::<Vec<_>>()),
+        )
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Checks if the given position lies within the bounds of the grid.
+    pub fn in_bounds(&self, position: Position) -> bool {
+        position.x >= 0
+            && position.y >= 0
+            && (position.x as usize) < self.width
+            && (position.y as usize) < self.height
+    }
+
+    /// Returns the cell at `position`, or `None` if it is out of bounds.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.offset(position).map(|i| &self.cells[i])
+    }
+
+    /// Returns a mutable reference to the cell at `position`, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.offset(position).map(move |i| &mut self.cells[i])
+    }
+
+    /// Iterates over every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| {
+            (0..width).map(move |x| Position::new(x as isize, y as isize))
+        })
+    }
+
+    /// Iterates over every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Iterates over the rows of the grid as slices.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy on an empty grid.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the position of the first cell (row by row) matching `predicate`.
+    pub fn position_of<P>(&self, mut predicate: P) -> Option<Position>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    /// Builds a new grid of the same shape by converting every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Converts a position into an index of `cells`, if it is in bounds.
+    fn offset(&self, position: Position) -> Option<usize> {
+        if self.in_bounds(position) {
+            Some(position.y as usize * self.width + position.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a `width` x `height` grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `position` is out of bounds; use [`Grid::get`] to check instead.
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let error = Grid::parse("abc\nde\nfgh\n", |c| c).unwrap_err();
+        assert_eq!(error, RaggedRowError { row: 1, expected: 3, found: 2 });
+        assert_eq!(error.to_string(), "row 1 has 2 cells, expected 3");
+        assert!(Grid::from_rows([vec![1], vec![2, 3]]).is_err());
+    }
+
+    #[test]
+    fn cells_are_read_row_by_row() {
+        let grid = Grid::parse("ab\ncd\n\nef\n", |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[Position::new(1, 0)], 'b');
+        assert_eq!(grid[Position::new(0, 2)], 'e');
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [['a', 'b'], ['c', 'd'], ['e', 'f']]);
+        assert_eq!(grid.position_of(|&c| c == 'd'), Some(Position::new(1, 1)));
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase())[Position::new(1, 2)], 'F');
+    }
+
+    #[test]
+    fn positions_past_the_edges_are_out_of_bounds() {
+        let mut grid = Grid::filled(3, 2, 0);
+        for position in [(0, 0), (2, 0), (0, 1), (2, 1)] {
+            assert!(grid.in_bounds(Position::new(position.0, position.1)));
+        }
+        for position in [(-1, 0), (0, -1), (3, 0), (0, 2), (3, 2), (isize::MIN, 0)] {
+            let position = Position::new(position.0, position.1);
+            assert!(!grid.in_bounds(position) && grid.get(position).is_none(), "{:?}", position);
+        }
+
+        *grid.get_mut(Position::new(2, 1)).unwrap() = 5;
+        assert_eq!(grid.iter().filter(|&(_, &cell)| cell == 5).map(|(position, _)| position).collect::<Vec<_>>(), [Position::new(2, 1)]);
+    }
+
+    #[test]
+    fn empty_grids_have_no_cells() {
+        let grid = Grid::parse("", |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert!(grid.get(Position::new(0, 0)).is_none());
+        assert_eq!(Grid::parse("\n\n", |c| c), Ok(grid));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the grid")]
+    fn indexing_out_of_bounds_panics() {
+        let grid = Grid::filled(2, 2, ());
+        grid[Position::new(2, 0)]
+    }
+}
@@ -0,0 +1,27 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The file every challenge reads its puzzle input from when no path is given.
+pub const DEFAULT_INPUT: &str = "puzzle_input.txt";
+
+/// Returns the path of the puzzle input.
+///
+/// The first command line argument is used if present, otherwise it falls back
+/// to [`DEFAULT_INPUT`] in the current directory.
+pub fn input_path() -> PathBuf {
+    env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT))
+}
+
+/// Reads the whole file into a string.
+///
+/// # Arguments
+/// * `filename` - Path to the file to be read.
+pub fn read_input<P>(filename: P) -> io::Result<String>
+where P: AsRef<Path>, {
+    fs::read_to_string(filename)
+}
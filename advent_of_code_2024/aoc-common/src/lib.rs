@@ -0,0 +1,19 @@
+//! Shared toolkit for the Advent of Code 2024 challenges.
+//!
+//! Every day crate builds on the same small set of helpers:
+//! - [`input`]: locating and reading the puzzle input;
+//! - [`grid`]: a typed, bounds-checked 2D [`Grid`];
+//! - [`position`]: the [`Position`] vector type and compass [`Direction`]s;
+//! - [`parse`]: parsing lists of numbers out of a line of text;
+//! - [`solution`]: the [`Solution`] trait every day implements, and the code
+//!   to run and time it.
+
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod position;
+pub mod solution;
+
+pub use grid::Grid;
+pub use position::{Direction, Position};
+pub use solution::{Answer, Part, Solution};
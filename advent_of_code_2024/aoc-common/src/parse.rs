@@ -0,0 +1,68 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when a token of a list is not a valid number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberError<E> {
+    pub token: String, // The token that failed to parse, as written
+    pub source: E,     // Why it failed
+}
+
+impl<E: fmt::Display> fmt::Display for NumberError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a valid number: {}", self.token, self.source)
+    }
+}
+
+impl<E: Error + 'static> Error for NumberError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Parses one token, keeping it in the error if it is not a valid `T`
+fn parse_token<T: FromStr>(token: &str) -> Result<T, NumberError<T::Err>> {
+    token.parse().map_err(|source| NumberError { token: token.to_string(), source })
+}
+
+/// Parses every whitespace-separated token of `line` as a number.
+///
+/// Fails on the first token that is not a valid `T`.
+pub fn parse_numbers<T: FromStr>(line: &str) -> Result<Vec<T>, NumberError<T::Err>> {
+    line.split_whitespace().map(parse_token).collect()
+}
+
+/// Parses a `separator`-delimited list of numbers, e.g. `75,47,61`.
+///
+/// Each item is trimmed before parsing, so `75, 47 ,61` is accepted as well.
+pub fn parse_separated<T: FromStr>(line: &str, separator: char) -> Result<Vec<T>, NumberError<T::Err>> {
+    line.split(separator).map(|item| parse_token(item.trim())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_split_on_any_whitespace() {
+        assert_eq!(parse_numbers::<i32>(" 7 6\t4  -2 "), Ok(vec![7, 6, 4, -2]));
+        assert_eq!(parse_numbers::<u64>(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn separated_items_are_trimmed() {
+        assert_eq!(parse_separated::<i32>("75, 47 ,61", ','), Ok(vec![75, 47, 61]));
+        assert_eq!(parse_separated::<i32>("1|2", '|'), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn errors_name_the_bad_token() {
+        let error = parse_numbers::<u8>("1 2 x 4").unwrap_err();
+        assert_eq!(error.token, "x");
+        assert_eq!(error.to_string(), "\"x\" is not a valid number: invalid digit found in string");
+
+        assert_eq!(parse_separated::<u8>("1,,3", ',').unwrap_err().token, "");
+        assert_eq!(parse_numbers::<u8>("256").unwrap_err().token, "256");
+    }
+}
@@ -0,0 +1,179 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point (or a displacement vector) on a 2D grid.
+///
+/// `x` grows to the right (columns) and `y` grows downwards (rows), matching
+/// the way puzzle inputs are read line by line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub x: isize, // Column
+    pub y: isize, // Row
+}
+
+impl Position {
+    /// Constructs a new position from its column and row.
+    pub const fn new(x: isize, y: isize) -> Self {
+        Position { x, y }
+    }
+
+    /// Returns the position one step away in the given direction.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, other: Position) -> Position {
+        Position::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Position {
+    fn add_assign(&mut self, other: Position) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, other: Position) -> Position {
+        Position::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Position {
+    fn sub_assign(&mut self, other: Position) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Position {
+    type Output = Position;
+
+    /// Rotates the vector by 180 degrees.
+    fn neg(self) -> Position {
+        Position::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Position {
+    type Output = Position;
+
+    fn mul(self, factor: isize) -> Position {
+        Position::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// The eight compass directions, listed clockwise starting from `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise from `Up`.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The four diagonal directions, clockwise from `UpRight`.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    /// All eight directions, clockwise from `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Returns the unit vector of one step in this direction.
+    pub fn offset(self) -> Position {
+        match self {
+            Direction::Up => Position::new(0, -1),
+            Direction::UpRight => Position::new(1, -1),
+            Direction::Right => Position::new(1, 0),
+            Direction::DownRight => Position::new(1, 1),
+            Direction::Down => Position::new(0, 1),
+            Direction::DownLeft => Position::new(-1, 1),
+            Direction::Left => Position::new(-1, 0),
+            Direction::UpLeft => Position::new(-1, -1),
+        }
+    }
+
+    /// Rotates the direction 90 degrees to the right (clockwise)
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Rotates the direction by 180 degrees
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// Rotates clockwise by `eighths` multiples of 45 degrees.
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % Direction::ALL.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directions_are_listed_clockwise() {
+        for (i, direction) in Direction::ALL.into_iter().enumerate() {
+            assert_eq!(direction.turn_right(), Direction::ALL[(i + 2) % 8]);
+            assert_eq!(direction.opposite(), Direction::ALL[(i + 4) % 8]);
+            assert_eq!(direction.opposite().offset(), -direction.offset());
+
+            // Turning right maps the offset (x, y) to (-y, x), as y grows downwards
+            let offset = direction.offset();
+            assert_eq!(direction.turn_right().offset(), Position::new(-offset.y, offset.x));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+    }
+
+    #[test]
+    fn direction_sets_split_all_directions() {
+        let mut both: Vec<Direction> = Direction::ORTHOGONAL.into_iter().chain(Direction::DIAGONAL).collect();
+        both.sort_by_key(|&direction| direction as usize);
+        assert_eq!(both, Direction::ALL);
+        assert!(Direction::ORTHOGONAL.iter().all(|direction| direction.offset().x == 0 || direction.offset().y == 0));
+        assert!(Direction::DIAGONAL.iter().all(|direction| direction.offset().x != 0 && direction.offset().y != 0));
+    }
+
+    #[test]
+    fn positions_add_like_vectors() {
+        let mut position = Position::new(3, -2);
+        assert_eq!(position.step(Direction::DownLeft), Position::new(2, -1));
+        assert_eq!(position * 3 - Position::new(1, 1), Position::new(8, -7));
+        position += Position::new(1, 2);
+        position -= Position::new(4, 0);
+        assert_eq!(position, Position::default());
+    }
+}
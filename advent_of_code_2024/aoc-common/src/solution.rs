@@ -0,0 +1,304 @@
+use std::fmt;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::input::read_input;
+
+/// Error type used by solvers to report invalid puzzle input.
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// The two halves of a daily puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    /// Converts `1` or `2` into the corresponding part.
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// Returns `1` or `2`.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part {}", self.number())
+    }
+}
+
+/// The answer produced by one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// The part has not been solved yet.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+/// A solver for one day of the advent calendar.
+///
+/// The input is parsed once and shared by both parts, so that parsing and
+/// solving can be timed separately.
+pub trait Solution {
+    /// Day of the advent calendar (1-25).
+    const DAY: u8;
+    /// Title of the puzzle.
+    const TITLE: &'static str;
+
+    /// Parsed representation of the puzzle input.
+    type Input;
+
+    /// Parses the raw puzzle input.
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    /// Solves the first part of the puzzle.
+    fn part1(input: &Self::Input) -> Result<Answer, Error>;
+
+    /// Solves the second part of the puzzle.
+    fn part2(input: &Self::Input) -> Result<Answer, Error>;
+}
+
+/// The answer to one part together with the time it took to compute.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Result<Answer, Error>,
+    pub elapsed: Duration,
+}
+
+/// The outcome of running a solver on an input.
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    /// Returns the answer to `part`, if it was run and succeeded.
+    pub fn answer(&self, part: Part) -> Option<&Answer> {
+        self.parts
+            .iter()
+            .find(|report| report.part == part)
+            .and_then(|report| report.answer.as_ref().ok())
+    }
+
+    /// Checks whether every part that was run produced an answer.
+    pub fn is_ok(&self) -> bool {
+        self.parts.iter().all(|report| report.answer.is_ok())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Day {:02}: {}", self.day, self.title)?;
+        writeln!(f, "  {:<8} {:>20} ({:.2?})", "parse", "", self.parse_time)?;
+        for report in &self.parts {
+            match &report.answer {
+                Ok(answer) => writeln!(
+                    f,
+                    "  {:<8} {:>20} ({:.2?})",
+                    report.part.to_string(),
+                    answer.to_string(),
+                    report.elapsed
+                )?,
+                Err(e) => writeln!(f, "  {:<8} error: {}", report.part.to_string(), e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses `input` with `S` and runs the requested parts, timing every phase.
+///
+/// Fails only if the input cannot be parsed; errors of the individual parts
+/// are recorded in the report.
+pub fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Report, Error> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&parsed),
+                Part::Two => S::part2(&parsed),
+            };
+            PartReport { part, answer, elapsed: start.elapsed() }
+        })
+        .collect();
+
+    Ok(Report { day: S::DAY, title: S::TITLE, parse_time, parts })
+}
+
+/// Type-erased handle on a [`Solution`], so that days can be listed and run
+/// without knowing their input types.
+#[derive(Debug, Clone, Copy)]
+pub struct Registration {
+    pub day: u8,
+    pub title: &'static str,
+    pub run: fn(&str, &[Part]) -> Result<Report, Error>,
+}
+
+impl Registration {
+    /// Registers the solver `S`.
+    pub fn of<S: Solution>() -> Self {
+        Registration { day: S::DAY, title: S::TITLE, run: run::<S> }
+    }
+}
+
+/// Reads the puzzle stored at `path`, printing why if it cannot
+fn read_puzzle(path: &Path) -> Option<String> {
+    read_input(path)
+        .map_err(|e| eprintln!("Failed to read {}: {}", path.display(), e))
+        .ok()
+}
+
+/// Entry point shared by the per-day binaries: solves both parts of the
+/// puzzle stored at `path` and prints the report.
+pub fn run_main<S: Solution>(path: impl AsRef<Path>) -> ExitCode {
+    let path = path.as_ref();
+    let Some(input) = read_puzzle(path) else {
+        return ExitCode::FAILURE;
+    };
+
+    match run::<S>(&input, &Part::BOTH) {
+        Ok(report) => {
+            print!("{}", report);
+            if report.is_ok() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Entry point for the other modes of the per-day binaries: parses the
+/// puzzle stored at `path` with `S` and hands it to `mode`, reporting
+/// errors like `run_main` and `exit_code`.
+pub fn run_with<S: Solution>(path: impl AsRef<Path>, mode: impl FnOnce(&S::Input) -> Result<(), Error>) -> ExitCode {
+    let path = path.as_ref();
+    let Some(input) = read_puzzle(path) else {
+        return ExitCode::FAILURE;
+    };
+
+    match S::parse(&input) {
+        Ok(parsed) => exit_code(mode(&parsed)),
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Exit code of a per-day binary whose mode finished with `result`,
+/// printing the error if there is one.
+pub fn exit_code(result: Result<(), Error>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums the numbers of the input, and fails part 2 on purpose
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 25;
+        const TITLE: &'static str = "Sum";
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Vec<u32>, Error> {
+            Ok(crate::parse::parse_numbers(input)?)
+        }
+
+        fn part1(numbers: &Vec<u32>) -> Result<Answer, Error> {
+            Ok(numbers.iter().sum::<u32>().into())
+        }
+
+        fn part2(_: &Vec<u32>) -> Result<Answer, Error> {
+            Err("no part 2".into())
+        }
+    }
+
+    #[test]
+    fn reports_keep_the_answer_or_error_of_each_part() {
+        let report = run::<Sum>("1 2 3", &Part::BOTH).unwrap();
+        assert_eq!(report.answer(Part::One), Some(&Answer::Number(6)));
+        assert_eq!(report.answer(Part::Two), None);
+        assert!(!report.is_ok());
+        assert!(report.to_string().contains("part 2   error: no part 2"));
+
+        assert!(run::<Sum>("1 2 3", &[Part::One]).unwrap().is_ok());
+        assert!(run::<Sum>("1 x", &[Part::One]).is_err());
+    }
+
+    #[test]
+    fn parts_and_answers_display_as_printed() {
+        assert_eq!(Part::from_number(2).map(Part::number), Some(2));
+        assert_eq!(Part::from_number(3), None);
+        assert_eq!(Part::One.to_string(), "part 1");
+        assert_eq!(Answer::from(-5i64).to_string(), "-5");
+        assert_eq!(Answer::from("ok".to_string()).to_string(), "ok");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+}
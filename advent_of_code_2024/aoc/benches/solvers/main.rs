@@ -0,0 +1,113 @@
+//! Times parsing and both parts of every day, on its first example and on a
+//! synthetic large input, then writes the results to a JSON file so they can
+//! be compared between commits.
+//!
+//! The file is `target/criterion/solvers.json` in the workspace, or the path
+//! in the `AOC_BENCH_JSON` environment variable.
+
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use aoc::fixtures::{examples, workspace_dir};
+use aoc_common::solution::Solution;
+use criterion::{BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Value};
+
+mod synthetic;
+
+/// One timed step of a day, as named in the criterion output
+struct Measured {
+    day: u8,
+    step: &'static str,  // `parse`, `part1` or `part2`
+    input: &'static str, // `example` or `synthetic`
+    bytes: usize,        // Size of the input
+}
+
+impl Measured {
+    /// Directory criterion writes the estimates of this benchmark to
+    fn dir(&self, output: &Path) -> PathBuf {
+        output.join(format!("day{:02}", self.day)).join(self.step).join(self.input)
+    }
+}
+
+/// Benchmarks the parser and both parts of `S` on its first example and its synthetic input
+fn bench_day<S: Solution>(c: &mut Criterion, measured: &mut Vec<Measured>) {
+    let example = examples(S::DAY)
+        .ok()
+        .and_then(|fixtures| fixtures.into_iter().next())
+        .unwrap_or_else(|| panic!("day {:02} has no example", S::DAY))
+        .input;
+    let inputs = [("example", example), ("synthetic", synthetic::input(S::DAY).expect("every day has a generator"))];
+
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    for (name, input) in &inputs {
+        let parsed = S::parse(input).unwrap_or_else(|e| panic!("day {:02}: cannot parse the {} input: {}", S::DAY, name, e));
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| b.iter(|| S::parse(black_box(input))));
+        group.bench_with_input(BenchmarkId::new("part1", name), &parsed, |b, parsed| b.iter(|| S::part1(black_box(parsed))));
+        group.bench_with_input(BenchmarkId::new("part2", name), &parsed, |b, parsed| b.iter(|| S::part2(black_box(parsed))));
+
+        for step in ["parse", "part1", "part2"] {
+            measured.push(Measured { day: S::DAY, step, input: name, bytes: input.len() });
+        }
+    }
+    group.finish();
+}
+
+/// Reads the estimates criterion wrote during this run, skipping benchmarks that were filtered out
+fn estimates(measured: &Measured, output: &Path, started: SystemTime) -> Option<Value> {
+    let path = measured.dir(output).join("new").join("estimates.json");
+    let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?;
+    if modified < started {
+        return None; // Left over from an earlier run
+    }
+
+    let estimates: Value = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+    let nanoseconds = |statistic: &str| estimates[statistic]["point_estimate"].clone();
+    Some(json!({
+        "day": measured.day,
+        "step": measured.step,
+        "input": measured.input,
+        "bytes": measured.bytes,
+        "mean_ns": nanoseconds("mean"),
+        "median_ns": nanoseconds("median"),
+        "std_dev_ns": nanoseconds("std_dev"),
+    }))
+}
+
+fn main() {
+    let output = workspace_dir().join("target").join("criterion");
+    let started = SystemTime::now();
+
+    let mut c = Criterion::default().output_directory(&output).configure_from_args();
+    let mut measured = Vec::new();
+    bench_day::<challenge_01::Day01>(&mut c, &mut measured);
+    bench_day::<challenge_02::Day02>(&mut c, &mut measured);
+    bench_day::<challenge_03::Day03>(&mut c, &mut measured);
+    bench_day::<challenge_04::Day04>(&mut c, &mut measured);
+    bench_day::<challenge_05::Day05>(&mut c, &mut measured);
+    bench_day::<challenge_06::Day06>(&mut c, &mut measured);
+    bench_day::<challenge_07::Day07>(&mut c, &mut measured);
+    bench_day::<challenge_08::Day08>(&mut c, &mut measured);
+    bench_day::<challenge_09::Day09>(&mut c, &mut measured);
+    c.final_summary();
+
+    let results: Vec<Value> = measured.iter().filter_map(|measured| estimates(measured, &output, started)).collect();
+    if results.is_empty() {
+        return; // Nothing was measured, e.g. when run by `cargo test --benches`
+    }
+
+    let timestamp = started.duration_since(SystemTime::UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0);
+    let report = json!({ "timestamp": timestamp, "benchmarks": results });
+    let path = env::var_os("AOC_BENCH_JSON").map(PathBuf::from).unwrap_or_else(|| output.join("solvers.json"));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("cannot create the directory of the JSON results");
+    }
+    fs::write(&path, serde_json::to_string_pretty(&report).expect("the results are valid JSON"))
+        .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+    eprintln!("Wrote {} results to {}", results.len(), path.display());
+}
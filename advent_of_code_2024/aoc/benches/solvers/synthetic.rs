@@ -0,0 +1,246 @@
+//! Large, deterministic puzzle inputs for benchmarking.
+//!
+//! Every generator produces an input in the format of its day, a few times
+//! larger than a real puzzle input, and always the same one so timings can
+//! be compared between commits.
+
+use std::fmt::Write;
+
+use aoc_common::solution::Solution;
+use challenge_06::Day06;
+
+/// A small linear congruential generator; good enough to scatter puzzle data
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    /// A number in `0..bound`
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+
+    /// A number in `low..=high`
+    fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    /// Whether an event with the given chance in percent happens
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// Returns the synthetic input of `day`, or `None` if there is no generator for it.
+pub fn input(day: u8) -> Option<String> {
+    let input = match day {
+        1 => location_lists(),
+        2 => reports(),
+        3 => corrupted_memory(),
+        4 => word_search(),
+        5 => print_queue(),
+        6 => lab(),
+        7 => calibrations(),
+        8 => antennas(),
+        9 => disk_map(),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Day 1: 100 000 pairs of five-digit location IDs
+fn location_lists() -> String {
+    let mut rng = Rng::new(1);
+    let mut input = String::new();
+    for _ in 0..100_000 {
+        writeln!(input, "{}   {}", rng.between(10_000, 99_999), rng.between(10_000, 99_999)).unwrap();
+    }
+    input
+}
+
+/// Day 2: 50 000 reports of 5 to 8 levels, about half of them safe
+fn reports() -> String {
+    let mut rng = Rng::new(2);
+    let mut input = String::new();
+    for _ in 0..50_000 {
+        let len = rng.between(5, 8);
+        let decreasing = rng.chance(50);
+        let mut level = rng.between(20, 70) as i64;
+        let mut levels = Vec::new();
+        for _ in 0..len {
+            levels.push(level.to_string());
+            // Mostly steps of 1 to 3 in one direction, sometimes a bad one
+            let step = if rng.chance(8) { rng.between(0, 6) as i64 - 2 } else { rng.between(1, 3) as i64 };
+            level += if decreasing { -step } else { step };
+        }
+        writeln!(input, "{}", levels.join(" ")).unwrap();
+    }
+    input
+}
+
+/// Day 3: about 1 MiB of corrupted memory with `mul`, `do()` and `don't()` instructions
+fn corrupted_memory() -> String {
+    const NOISE: &[&str] = &["mul(", "mul[3,4]", "do(", "don't", "what()", "select(", ")", ",", "#", "'", " ", "%&"];
+
+    let mut rng = Rng::new(3);
+    let mut input = String::new();
+    while input.len() < 1 << 20 {
+        match rng.below(10) {
+            0..=3 => write!(input, "mul({},{})", rng.between(1, 999), rng.between(1, 999)).unwrap(),
+            4 => input.push_str(if rng.chance(50) { "do()" } else { "don't()" }),
+            _ => input.push_str(NOISE[rng.below(NOISE.len() as u64) as usize]),
+        }
+        if rng.chance(2) {
+            input.push('\n');
+        }
+    }
+    input.push('\n');
+    input
+}
+
+/// Day 4: a 500 x 500 grid of the letters of `XMAS`
+fn word_search() -> String {
+    const LETTERS: [char; 4] = ['X', 'M', 'A', 'S'];
+
+    let mut rng = Rng::new(4);
+    let mut input = String::new();
+    for _ in 0..500 {
+        input.extend((0..500).map(|_| LETTERS[rng.below(4) as usize]));
+        input.push('\n');
+    }
+    input
+}
+
+/// Day 5: 49 pages with a rule between every pair, and 2 000 updates of 5 to 23 pages
+fn print_queue() -> String {
+    let mut rng = Rng::new(5);
+    let mut pages: Vec<u64> = (11..=99).step_by(2).chain([10, 12, 14, 16]).collect();
+
+    // Shuffle the pages so the rules are not sorted by page number
+    for i in (1..pages.len()).rev() {
+        pages.swap(i, rng.below(i as u64 + 1) as usize);
+    }
+
+    let mut input = String::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            writeln!(input, "{}|{}", before, after).unwrap();
+        }
+    }
+    input.push('\n');
+
+    for _ in 0..2_000 {
+        let mut pool: Vec<usize> = (0..pages.len()).collect();
+        let len = 5 + 2 * rng.below(10);
+        let mut update: Vec<usize> = (0..len).map(|_| pool.swap_remove(rng.below(pool.len() as u64) as usize)).collect();
+
+        // Leave about 40% of the updates in the right order
+        if rng.chance(40) {
+            update.sort_unstable();
+        }
+
+        let update: Vec<String> = update.iter().map(|&i| pages[i].to_string()).collect();
+        writeln!(input, "{}", update.join(",")).unwrap();
+    }
+    input
+}
+
+/// Day 6: a 130 x 130 lab, the size of a real one, with about 3% of obstacles.
+///
+/// Random obstacles let the guard out quickly, so seeds are tried in order
+/// until it visits at least 1 000 cells before leaving, giving the
+/// obstruction search a long patrol to work on.
+fn lab() -> String {
+    (6..)
+        .map(lab_with_seed)
+        .find(|input| Day06::parse(input).is_ok_and(|lab| lab.patrol_length().is_some_and(|visited| visited >= 1_000)))
+        .expect("some seed gives a long patrol")
+}
+
+/// A random 130 x 130 lab with the guard in the middle
+fn lab_with_seed(seed: u64) -> String {
+    const SIZE: usize = 130;
+
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let cell = if (x, y) == (SIZE / 2, SIZE / 2) {
+                '^'
+            } else if rng.chance(3) {
+                '#'
+            } else {
+                '.'
+            };
+            input.push(cell);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// Day 7: 2 000 equations of 3 to 12 numbers, most of them solvable
+fn calibrations() -> String {
+    let mut rng = Rng::new(7);
+    let mut input = String::new();
+    for _ in 0..2_000 {
+        let len = rng.between(3, 12);
+        let numbers: Vec<u64> = (0..len).map(|_| rng.between(1, 999)).collect();
+
+        // Build the target from random operators, falling back to a sum when it grows too large
+        let mut target = numbers[0];
+        for &number in &numbers[1..] {
+            let next = match rng.below(3) {
+                0 => target.checked_add(number),
+                1 => target.checked_mul(number),
+                _ => format!("{}{}", target, number).parse().ok(),
+            };
+            target = next.filter(|&value| value < 1 << 48).unwrap_or(target + number);
+        }
+        if rng.chance(30) {
+            target += 1;
+        }
+
+        let numbers: Vec<String> = numbers.iter().map(|number| number.to_string()).collect();
+        writeln!(input, "{}: {}", target, numbers.join(" ")).unwrap();
+    }
+    input
+}
+
+/// Day 8: a 200 x 200 map with 60 frequencies of 4 to 8 antennas each
+fn antennas() -> String {
+    const SIZE: usize = 200;
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWX";
+
+    let mut rng = Rng::new(8);
+    let mut cells = vec![b'.'; SIZE * SIZE];
+    for &frequency in FREQUENCIES {
+        for _ in 0..rng.between(4, 8) {
+            cells[rng.below((SIZE * SIZE) as u64) as usize] = frequency;
+        }
+    }
+
+    let mut input = String::new();
+    for row in cells.chunks(SIZE) {
+        input.push_str(std::str::from_utf8(row).expect("the map is ASCII"));
+        input.push('\n');
+    }
+    input
+}
+
+/// Day 9: a disk map of 40 001 digits, twice as long as a real one
+fn disk_map() -> String {
+    let mut rng = Rng::new(9);
+    let mut input: String = (0..40_001)
+        .map(|i| {
+            // Files are never empty, gaps can be
+            let low = if i % 2 == 0 { 1 } else { 0 };
+            char::from_digit(rng.between(low, 9) as u32, 10).expect("a single digit")
+        })
+        .collect();
+    input.push('\n');
+    input
+}
@@ -0,0 +1,136 @@
+//! Inputs with known answers, used to guard the solvers against regressions.
+//!
+//! Every `challenge-XX/examples/` directory holds one or more `<name>.txt`
+//! inputs taken from the puzzle description, each next to a `<name>.answers`
+//! file listing the expected answers:
+//!
+//! ```text
+//! part1: 143
+//! part2: 123
+//! ```
+//!
+//! The answers to the real puzzle input are stored the same way, in
+//! `challenge-XX/puzzle_input.answers`. Parts missing from a file are not checked.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::input::DEFAULT_INPUT;
+use aoc_common::solution::{Part, Registration};
+
+use crate::day_dir;
+
+/// Extension of the files holding the expected answers.
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+/// An input together with the answers it is expected to produce.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub name: String,                // File name of the input, without extension
+    pub input: String,               // Contents of the input file
+    pub answers: Vec<(Part, String)>, // Expected answer of each checked part
+}
+
+/// Absolute path of the workspace root.
+pub fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate lives inside the workspace")
+        .to_path_buf()
+}
+
+/// Loads every example of `day`, sorted by name.
+///
+/// Every example must have an answers file; a missing one is reported as an error.
+pub fn examples(day: u8) -> io::Result<Vec<Fixture>> {
+    let dir = workspace_dir().join(day_dir(day)).join("examples");
+
+    let mut inputs: Vec<PathBuf> = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    inputs.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    inputs.sort();
+
+    inputs.iter().map(|path| load(path)).collect()
+}
+
+/// Loads the real puzzle input of `day` with its stored answers.
+///
+/// Returns `None` when the input or its answers file is not present, since
+/// puzzle inputs are personal and not committed to the repository.
+pub fn puzzle_input(day: u8) -> io::Result<Option<Fixture>> {
+    let path = workspace_dir().join(day_dir(day)).join(DEFAULT_INPUT);
+    if !path.exists() || !path.with_extension(ANSWERS_EXTENSION).exists() {
+        return Ok(None);
+    }
+    load(&path).map(Some)
+}
+
+/// Parses an answers file made of `part1: <answer>` and `part2: <answer>` lines.
+///
+/// Blank lines and lines starting with `#` are ignored.
+pub fn read_answers(path: &Path) -> io::Result<Vec<(Part, String)>> {
+    let invalid = |line: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: expected 'part1: <answer>' or 'part2: <answer>', found {:?}", path.display(), line),
+        )
+    };
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+
+    let mut answers = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, answer) = line.split_once(':').ok_or_else(|| invalid(line))?;
+        let part = match key.trim() {
+            "part1" => Part::One,
+            "part2" => Part::Two,
+            _ => return Err(invalid(line)),
+        };
+        answers.push((part, answer.trim().to_string()));
+    }
+
+    Ok(answers)
+}
+
+/// Runs both parts of `registration` on the fixture and describes every
+/// answer that differs from the expected one. An empty result means success.
+pub fn check(registration: &Registration, fixture: &Fixture) -> Vec<String> {
+    let label = format!("day {:02} ({})", registration.day, fixture.name);
+
+    let report = match (registration.run)(&fixture.input, &Part::BOTH) {
+        Ok(report) => report,
+        Err(e) => return vec![format!("{}: failed to parse input: {}", label, e)],
+    };
+
+    let mut problems = Vec::new();
+    for (part, expected) in &fixture.answers {
+        let outcome = report.parts.iter().find(|report| report.part == *part);
+        match outcome.map(|report| &report.answer) {
+            Some(Ok(answer)) if answer.to_string() == *expected => {}
+            Some(Ok(answer)) => problems.push(format!(
+                "{} {}: expected {}, got {}",
+                label, part, expected, answer
+            )),
+            Some(Err(e)) => problems.push(format!("{} {}: failed: {}", label, part, e)),
+            None => problems.push(format!("{} {}: not run", label, part)),
+        }
+    }
+    problems
+}
+
+/// Reads an input file and the answers file next to it.
+fn load(path: &Path) -> io::Result<Fixture> {
+    Ok(Fixture {
+        name: path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+        input: fs::read_to_string(path)?,
+        answers: read_answers(&path.with_extension(ANSWERS_EXTENSION))?,
+    })
+}
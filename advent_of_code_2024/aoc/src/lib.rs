@@ -0,0 +1,41 @@
+//! Registry of every day solver, shared by the `aoc` binary and the
+//! regression tests.
+
+pub mod fixtures;
+
+use std::path::PathBuf;
+
+use aoc_common::input::DEFAULT_INPUT;
+use aoc_common::solution::Registration;
+
+/// Returns every registered solver, ordered by day.
+///
+/// New days only need to be added here to be picked up by `aoc run`.
+pub fn registry() -> Vec<Registration> {
+    vec![
+        Registration::of::<challenge_01::Day01>(),
+        Registration::of::<challenge_02::Day02>(),
+        Registration::of::<challenge_03::Day03>(),
+        Registration::of::<challenge_04::Day04>(),
+        Registration::of::<challenge_05::Day05>(),
+        Registration::of::<challenge_06::Day06>(),
+        Registration::of::<challenge_07::Day07>(),
+        Registration::of::<challenge_08::Day08>(),
+        Registration::of::<challenge_09::Day09>(),
+    ]
+}
+
+/// Looks up the solver registered for `day`.
+pub fn find(day: u8) -> Option<Registration> {
+    registry().into_iter().find(|registration| registration.day == day)
+}
+
+/// Directory of the crate solving `day`, relative to the workspace root.
+pub fn day_dir(day: u8) -> PathBuf {
+    PathBuf::from(format!("challenge-{:02}", day))
+}
+
+/// Default location of the puzzle input for `day`, relative to the workspace root.
+pub fn default_input_path(day: u8) -> PathBuf {
+    day_dir(day).join(DEFAULT_INPUT)
+}
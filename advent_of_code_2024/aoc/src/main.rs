@@ -0,0 +1,117 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc::{default_input_path, find, registry};
+use aoc_common::input::read_input;
+use aoc_common::solution::{Part, Registration};
+use clap::{ArgGroup, Args, Parser, Subcommand};
+
+/// Runs the Advent of Code 2024 solvers.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves one day, or every registered day in sequence
+    Run(RunArgs),
+    /// Lists the registered days
+    List,
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("days").required(true).args(["day", "all"])))]
+struct RunArgs {
+    /// Day to solve
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Solve every registered day
+    #[arg(long)]
+    all: bool,
+
+    /// Only solve this part (both parts by default)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input file (defaults to challenge-XX/puzzle_input.txt)
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::List => {
+            for registration in registry() {
+                println!("Day {:02}: {}", registration.day, registration.title);
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// Runs the selected days and prints a labeled report for each one.
+fn run(args: RunArgs) -> ExitCode {
+    let parts = match args.part.and_then(Part::from_number) {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let days = match args.day {
+        Some(day) => match find(day) {
+            Some(registration) => vec![registration],
+            None => {
+                eprintln!("Day {} has no registered solver", day);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => registry(),
+    };
+
+    let mut failures = 0;
+    let start = Instant::now();
+
+    for registration in &days {
+        let path = args.input.clone().unwrap_or_else(|| default_input_path(registration.day));
+        match run_day(registration, &path, &parts) {
+            Ok(true) => {}
+            Ok(false) => failures += 1,
+            // With `--all`, days without an input are skipped rather than failed
+            Err(e) if args.all && e.kind() == io::ErrorKind::NotFound => {
+                println!("Day {:02}: skipped, no input at {}", registration.day, path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", path.display(), e);
+                failures += 1;
+            }
+        }
+    }
+
+    if args.all {
+        println!("Ran {} days in {:.2?}, {} failed", days.len(), start.elapsed(), failures);
+    }
+
+    if failures == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+/// Solves a single day, returning whether every part succeeded.
+fn run_day(registration: &Registration, path: &Path, parts: &[Part]) -> io::Result<bool> {
+    let input = read_input(path)?;
+
+    match (registration.run)(&input, parts) {
+        Ok(report) => {
+            print!("{}", report);
+            Ok(report.is_ok())
+        }
+        Err(e) => {
+            eprintln!("Day {:02}: failed to parse {}: {}", registration.day, path.display(), e);
+            Ok(false)
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Golden-answer regression tests: every registered solver is run on the
+//! examples of its day and, when present, on the real puzzle input, and
+//! the answers are compared with the stored ones.
+
+use aoc::fixtures::{check, examples, puzzle_input};
+use aoc::registry;
+
+#[test]
+fn every_day_has_examples() {
+    for registration in registry() {
+        let fixtures = examples(registration.day)
+            .unwrap_or_else(|e| panic!("day {:02}: cannot load examples: {}", registration.day, e));
+        assert!(!fixtures.is_empty(), "day {:02} has no examples", registration.day);
+    }
+}
+
+#[test]
+fn examples_match_expected_answers() {
+    let mut problems = Vec::new();
+
+    for registration in registry() {
+        match examples(registration.day) {
+            Ok(fixtures) => {
+                for fixture in fixtures {
+                    problems.extend(check(&registration, &fixture));
+                }
+            }
+            Err(e) => problems.push(format!("day {:02}: cannot load examples: {}", registration.day, e)),
+        }
+    }
+
+    assert!(problems.is_empty(), "answers drifted:\n{}", problems.join("\n"));
+}
+
+#[test]
+fn puzzle_inputs_match_stored_answers() {
+    let mut problems = Vec::new();
+
+    for registration in registry() {
+        match puzzle_input(registration.day) {
+            Ok(Some(fixture)) => problems.extend(check(&registration, &fixture)),
+            Ok(None) => eprintln!("day {:02}: no puzzle input with stored answers, skipped", registration.day),
+            Err(e) => problems.push(format!("day {:02}: cannot load puzzle input: {}", registration.day, e)),
+        }
+    }
+
+    assert!(problems.is_empty(), "answers drifted:\n{}", problems.join("\n"));
+}
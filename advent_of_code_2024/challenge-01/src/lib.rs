@@ -0,0 +1,229 @@
+pub mod parse;
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use aoc_common::solution::{Answer, Error, Solution};
+
+pub use parse::{parse_pairs, InputError, ParseMode};
+
+/// A struct to hold and process two lists of integers for comparison and scoring
+///
+/// Works with any integer type that fits in an `i128`; results are computed
+/// in 128 bits so they cannot overflow the type of the lists, and the sums
+/// that could still overflow 128 bits are checked.
+pub struct IntegerPairProcessor<T = i32> {
+    list_a: Vec<T>, // Represents the first list of integers
+    list_b: Vec<T>, // Represents the second list of integers
+}
+
+impl<T> IntegerPairProcessor<T>
+where
+    T: Copy + Ord + Hash + Into<i128>,
+{
+    /// Creates a processor from the two lists, sorting them for ordered comparisons
+    ///
+    /// The lists are compared element by element, so they must have the same length.
+    pub fn new(list_a: Vec<T>, list_b: Vec<T>) -> Result<Self, InputError> {
+        if list_a.len() != list_b.len() {
+            return Err(InputError::UnequalLengths { first: list_a.len(), second: list_b.len() });
+        }
+
+        let mut processor = IntegerPairProcessor { list_a, list_b };
+        processor.sort_lists();
+        Ok(processor)
+    }
+
+    /// Sorts both lists in ascending order for ordered comparisons
+    fn sort_lists(&mut self) {
+        self.list_a.sort_unstable();
+        self.list_b.sort_unstable();
+    }
+
+    /// Iterates over the distances between corresponding elements of the sorted lists
+    ///
+    /// Two `i128` can be further apart than `i128::MAX`, but never than `u128::MAX`.
+    fn distances(&self) -> impl Iterator<Item = u128> + '_ {
+        self.list_a.iter()
+            .zip(self.list_b.iter()) // Pair up elements from both lists
+            .map(|(&first, &second)| first.into().abs_diff(second.into())) // Compute absolute difference
+    }
+
+    /// Calculates the total sum of absolute differences between corresponding elements in both lists
+    ///
+    /// Returns `None` if the total does not fit in an `i128`.
+    pub fn total_absolute_difference(&self) -> Option<i128> {
+        self.distances()
+            .try_fold(0i128, |total, distance| total.checked_add(i128::try_from(distance).ok()?))
+    }
+
+    /// Calculates a similarity score based on the frequency of elements in one list matching elements in the other
+    ///
+    /// Counts the elements of `list_b` once, so it runs in linear time.
+    /// Returns `None` if the score does not fit in an `i128`.
+    pub fn similarity_score(&self) -> Option<i128> {
+        let occurrences = count_occurrences(&self.list_b);
+
+        self.list_a.iter().try_fold(0i128, |score, first| {
+            let count = occurrences.get(first).copied().unwrap_or(0); // Occurrences of `first` in `list_b`
+            let contribution = (*first).into().checked_mul(count as i128)?; // Multiply the value by its occurrence count
+            score.checked_add(contribution)
+        })
+    }
+
+    /// The median distance between corresponding elements of the sorted lists,
+    /// or `None` if the lists are empty
+    pub fn median_distance(&self) -> Option<f64> {
+        let mut distances: Vec<u128> = self.distances().collect();
+        distances.sort_unstable();
+
+        let middle = distances.len() / 2;
+        match distances.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(distances[middle] as f64),
+            _ => Some((distances[middle - 1] as f64 + distances[middle] as f64) / 2.0),
+        }
+    }
+
+    /// The largest distance between corresponding elements of the sorted lists,
+    /// or `None` if the lists are empty
+    pub fn max_distance(&self) -> Option<u128> {
+        self.distances().max()
+    }
+
+    /// The Jaccard index of the two lists seen as sets: the number of distinct
+    /// values in both lists over the number of distinct values in either.
+    ///
+    /// Returns `None` if both lists are empty.
+    pub fn jaccard_overlap(&self) -> Option<f64> {
+        let set_a: HashSet<T> = self.list_a.iter().copied().collect();
+        let set_b: HashSet<T> = self.list_b.iter().copied().collect();
+
+        let union = set_a.union(&set_b).count();
+        let intersection = set_a.intersection(&set_b).count();
+        (union > 0).then(|| intersection as f64 / union as f64)
+    }
+}
+
+/// Solver for Day 1: Historian Hysteria
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    type Input = IntegerPairProcessor;
+
+    fn parse(input: &str) -> Result<IntegerPairProcessor, Error> {
+        Ok(parse_pairs(input, ParseMode::Strict)?)
+    }
+
+    fn part1(processor: &IntegerPairProcessor) -> Result<Answer, Error> {
+        let total = processor.total_absolute_difference().ok_or("the total distance overflows an i128")?;
+        Ok(total.into())
+    }
+
+    fn part2(processor: &IntegerPairProcessor) -> Result<Answer, Error> {
+        let score = processor.similarity_score().ok_or("the similarity score overflows an i128")?;
+        Ok(score.into())
+    }
+}
+
+/// Solver for Day 1 that skips irregular lines instead of rejecting them
+pub struct Day01Lenient;
+
+impl Solution for Day01Lenient {
+    const DAY: u8 = Day01::DAY;
+    const TITLE: &'static str = Day01::TITLE;
+
+    type Input = IntegerPairProcessor;
+
+    fn parse(input: &str) -> Result<IntegerPairProcessor, Error> {
+        Ok(parse_pairs(input, ParseMode::Lenient)?)
+    }
+
+    fn part1(processor: &IntegerPairProcessor) -> Result<Answer, Error> {
+        Day01::part1(processor)
+    }
+
+    fn part2(processor: &IntegerPairProcessor) -> Result<Answer, Error> {
+        Day01::part2(processor)
+    }
+}
+
+/// Counts how many times every value appears in a list of integers.
+///
+/// # Arguments
+/// * `values` - The integers to count.
+///
+/// # Returns
+/// * A map from every distinct value to its number of occurrences.
+fn count_occurrences<T: Copy + Eq + Hash>(values: &[T]) -> HashMap<T, usize> {
+    let mut occurrences = HashMap::new();
+    for &value in values {
+        *occurrences.entry(value).or_insert(0) += 1;
+    }
+    occurrences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances_between_extreme_values_do_not_overflow() {
+        let processor = IntegerPairProcessor::new(vec![i128::MIN], vec![i128::MAX]).unwrap();
+        assert_eq!(processor.max_distance(), Some(u128::MAX));
+        assert_eq!(processor.median_distance(), Some(u128::MAX as f64));
+        assert_eq!(processor.total_absolute_difference(), None);
+
+        let processor = IntegerPairProcessor::new(vec![i64::MIN, i64::MAX], vec![i64::MAX, i64::MIN]).unwrap();
+        assert_eq!(processor.total_absolute_difference(), Some(0));
+        let processor = IntegerPairProcessor::new(vec![i64::MIN], vec![i64::MAX]).unwrap();
+        assert_eq!(processor.total_absolute_difference(), Some(u64::MAX as i128));
+    }
+
+    #[test]
+    fn totals_that_overflow_are_reported() {
+        let processor = IntegerPairProcessor::new(vec![i128::MAX, i128::MAX], vec![0, 0]).unwrap();
+        assert_eq!(processor.total_absolute_difference(), None);
+        assert_eq!(processor.similarity_score(), Some(0));
+
+        let processor = IntegerPairProcessor::new(vec![i128::MAX, i128::MAX], vec![i128::MAX, 0]).unwrap();
+        assert_eq!(processor.similarity_score(), None);
+    }
+
+    #[test]
+    fn statistics_of_empty_lists_are_missing() {
+        let processor = IntegerPairProcessor::<i32>::new(vec![], vec![]).unwrap();
+        assert_eq!(processor.median_distance(), None);
+        assert_eq!(processor.max_distance(), None);
+        assert_eq!(processor.jaccard_overlap(), None);
+        assert_eq!(processor.total_absolute_difference(), Some(0));
+    }
+
+    #[test]
+    fn statistics_of_the_example() {
+        // Sorted, the pairs are 1-3, 2-3, 3-3, 3-4, 3-5 and 4-9
+        let processor = IntegerPairProcessor::new(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]).unwrap();
+        assert_eq!(processor.max_distance(), Some(5));
+
+        // An even number of distances: the median is halfway between the two middle ones
+        assert_eq!(processor.median_distance(), Some(1.5));
+
+        // {3, 4} out of {1, 2, 3, 4, 5, 9}, however many times each value appears
+        assert_eq!(processor.jaccard_overlap(), Some(2.0 / 6.0));
+    }
+
+    #[test]
+    fn ties_give_the_tied_value() {
+        let processor = IntegerPairProcessor::new(vec![1, 5, 9], vec![3, 7, 11]).unwrap();
+        assert_eq!(processor.median_distance(), Some(2.0));
+        assert_eq!(processor.max_distance(), Some(2));
+        assert_eq!(processor.jaccard_overlap(), Some(0.0));
+
+        let processor = IntegerPairProcessor::new(vec![7, 7], vec![7, 7]).unwrap();
+        assert_eq!(processor.median_distance(), Some(0.0));
+        assert_eq!(processor.jaccard_overlap(), Some(1.0));
+    }
+}
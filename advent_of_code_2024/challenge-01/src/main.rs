@@ -1,95 +1,47 @@
-    
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-/// A struct to hold and process two lists of integers for comparison and scoring
-struct IntegerPairProcessor {
-    list_a: Vec<i32>, // Represents the first list of integers
-    list_b: Vec<i32>, // Represents the second list of integers
-
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_common::input::DEFAULT_INPUT;
+use aoc_common::solution::{run_main, run_with, Error};
+use challenge_01::{Day01, Day01Lenient, IntegerPairProcessor};
+use clap::Parser;
+
+/// Solves day 1, or describes how far apart the two lists are.
+#[derive(Parser)]
+#[command(name = "challenge-01")]
+struct Cli {
+    /// Puzzle input file
+    #[arg(default_value = DEFAULT_INPUT)]
+    input: PathBuf,
+
+    /// Skip lines that do not start with two integers instead of failing on them
+    #[arg(long)]
+    lenient: bool,
+
+    /// Print the median and largest distances and the overlap of the lists instead of the answers
+    #[arg(long)]
+    stats: bool,
 }
 
-impl IntegerPairProcessor {
-    /// Sorts both lists in ascending order for ordered comparisons
-    fn sort_lists(&mut self) {
-        self.list_a.sort_unstable();
-        self.list_b.sort_unstable();
-    }
-
-    /// Calculates the total sum of absolute differences between corresponding elements in both lists
-    /// Assumes both lists are sorted and of the same length.
-    fn total_absolute_difference(&self) -> i32 {
-        self.list_a.iter()
-        .zip(self.list_b.iter()) // Pair up elements from both lists
-        .map(|(first, second)| (first - second).abs()) // Compute absolute difference
-        .sum() // Sum up the differences 
-    }
-
-    /// Calculates a similarity score based on the frequency of elements in one list matching elements in the other
-    fn similarity_score(&self) -> i32 {
-       self.list_a.iter()
-            .map(|&first| {
-                let count = count_occurrences(&self.list_b, first); // Count occurrences of `first` in `list_b`
-                first * count as i32 // Multiply the value by its occurrence count
-            })
-            .sum() // Sum up all similarity contributions
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match (cli.lenient, cli.stats) {
+        (false, false) => run_main::<Day01>(&cli.input),
+        (true, false) => run_main::<Day01Lenient>(&cli.input),
+        (false, true) => run_with::<Day01>(&cli.input, stats),
+        (true, true) => run_with::<Day01Lenient>(&cli.input, stats),
     }
 }
 
-fn main() {
-
-    let mut processor = IntegerPairProcessor {
-        list_a: Vec::new(),
-        list_b: Vec::new(),
+fn stats(processor: &IntegerPairProcessor) -> Result<(), Error> {
+    let (Some(median), Some(max), Some(overlap)) =
+        (processor.median_distance(), processor.max_distance(), processor.jaccard_overlap())
+    else {
+        return Err("the lists are empty".into());
     };
 
-    // Read the input file line by line
-    if let Ok(lines) = read_lines("./puzzle_input.txt") {
-        for line in lines.flatten() {
-            // Split each lines into parts and parse them into integers       
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            
-            // Ensure the line has at least two parts
-            if parts.len() >= 2 {
-                // Parse integers and add to respective lists.
-                processor.list_a.push(parts[0].parse::<i32>().unwrap());
-                processor.list_b.push(parts[1].parse::<i32>().unwrap());
-            }
-        }
-    }
-
-    // Sort the lists before performing operations.
-    processor.sort_lists();
-
-    // Print results of the operations
-    println!("Total Absolute Difference: {}", processor.total_absolute_difference());
-    println!("Similarity Score: {}", processor.similarity_score());
-
+    println!("Median distance:  {}", median);
+    println!("Largest distance: {}", max);
+    println!("Overlap:          {:.1}% of the distinct values are in both lists", overlap * 100.0);
+    Ok(())
 }
-
-/// Reads lines from a file and returns an iterator over them.
-/// If the file cannot be opened, an error is returned.
-///
-/// # Arguments
-/// * `filename` - Path to the file to be read.
-///
-/// # Returns
-/// * An iterator over the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-/// Counts the number of occurrences of a target number in a vector of integers.
-///
-/// # Arguments
-/// * `vec` - A reference to a vector of integers.
-/// * `target` - The integer to count occurrences of.
-///
-/// # Returns
-/// * The number of times `target` appears in `vec`.
-fn count_occurrences(vec: &[i32], target: i32) -> usize {
-    vec.iter().filter(|&&x| x == target).count()
-}
\ No newline at end of file
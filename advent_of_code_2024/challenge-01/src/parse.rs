@@ -0,0 +1,189 @@
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::iter;
+use std::str::FromStr;
+
+use crate::IntegerPairProcessor;
+
+/// How irregular lines are handled when parsing the two lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Every line must hold exactly two integers
+    Strict,
+    /// Lines that do not start with two integers are skipped, and extra columns are ignored
+    Lenient,
+}
+
+/// Error returned when the two lists cannot be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    /// A token that is not a valid integer
+    InvalidNumber { line: usize, column: usize, token: String, reason: String },
+    /// A line with fewer than two columns
+    MissingColumn { line: usize, found: usize },
+    /// A line with more than two columns; `column` is where the first extra one starts
+    ExtraColumn { line: usize, column: usize },
+    /// The two lists do not have the same number of elements
+    UnequalLengths { first: usize, second: usize },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::InvalidNumber { line, column, token, reason } => {
+                write!(f, "line {}, column {}: {:?} is not a valid integer ({})", line, column, token, reason)
+            }
+            InputError::MissingColumn { line, found } => {
+                write!(f, "line {}: expected 2 columns, found {}", line, found)
+            }
+            InputError::ExtraColumn { line, column } => {
+                write!(f, "line {}, column {}: unexpected extra column", line, column)
+            }
+            InputError::UnequalLengths { first, second } => {
+                write!(f, "the lists have different lengths: {} and {}", first, second)
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Splits `line` into whitespace-separated tokens, each with its column (from 1)
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut chars = line.char_indices().enumerate();
+    iter::from_fn(move || {
+        // A token runs from its first non-whitespace character up to the next whitespace one
+        let (column, (start, _)) = chars.find(|(_, (_, c))| !c.is_whitespace())?;
+        let end = chars.find(|(_, (_, c))| c.is_whitespace()).map_or(line.len(), |(_, (offset, _))| offset);
+        Some((column + 1, &line[start..end]))
+    })
+}
+
+/// Parses the two lists, one pair of integers per line, e.g. `3   4`.
+///
+/// In `ParseMode::Strict` any line that is not exactly two integers is an
+/// error; in `ParseMode::Lenient` such lines are skipped instead, as long as
+/// they do not start with two integers, and extra columns are ignored.
+/// Blank lines after the last pair are ignored in both modes.
+pub fn parse_pairs<T>(input: &str, mode: ParseMode) -> Result<IntegerPairProcessor<T>, InputError>
+where
+    T: Copy + Ord + Hash + Into<i128> + FromStr,
+    T::Err: fmt::Display,
+{
+    let mut list_a = Vec::new();
+    let mut list_b = Vec::new();
+
+    // Leave out the blank lines at the end of the input
+    let lines: Vec<&str> = input.lines().collect();
+    let end = lines.iter().rposition(|text| !text.trim().is_empty()).map_or(0, |last| last + 1);
+
+    for (index, text) in lines[..end].iter().enumerate() {
+        let line = index + 1;
+        let columns: Vec<(usize, &str)> = tokens(text).collect();
+
+        // Parse the first two columns into integers
+        let parsed: Result<Vec<T>, InputError> = columns
+            .iter()
+            .take(2)
+            .map(|&(column, token)| {
+                token.parse::<T>().map_err(|e| InputError::InvalidNumber {
+                    line,
+                    column,
+                    token: token.to_string(),
+                    reason: e.to_string(),
+                })
+            })
+            .collect();
+
+        let pair = match (parsed, mode) {
+            (Ok(numbers), _) if numbers.len() == 2 => numbers,
+            (_, ParseMode::Lenient) => continue,
+            (Err(e), ParseMode::Strict) => return Err(e),
+            (Ok(_), ParseMode::Strict) => return Err(InputError::MissingColumn { line, found: columns.len() }),
+        };
+        if let (Some(&(column, _)), ParseMode::Strict) = (columns.get(2), mode) {
+            return Err(InputError::ExtraColumn { line, column });
+        }
+
+        // Add integers to respective lists.
+        list_a.push(pair[0]);
+        list_b.push(pair[1]);
+    }
+
+    IntegerPairProcessor::new(list_a, list_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(input: &str, mode: ParseMode) -> Result<(Vec<i32>, Vec<i32>), InputError> {
+        parse_pairs::<i32>(input, mode).map(|processor| (processor.list_a, processor.list_b))
+    }
+
+    #[test]
+    fn tokens_start_at_their_column() {
+        let found: Vec<(usize, &str)> = tokens("  3\t é4  -5").collect();
+        assert_eq!(found, [(3, "3"), (6, "é4"), (10, "-5")]);
+        assert_eq!(tokens(" \u{a0} ").count(), 0);
+    }
+
+    #[test]
+    fn strict_mode_wants_exactly_two_integers() {
+        assert_eq!(pairs("3   4\n1 2\n", ParseMode::Strict), Ok((vec![1, 3], vec![2, 4])));
+        assert_eq!(
+            pairs("3 4\n5\n", ParseMode::Strict),
+            Err(InputError::MissingColumn { line: 2, found: 1 })
+        );
+        assert_eq!(
+            pairs("3 4\n1 2  9\n", ParseMode::Strict),
+            Err(InputError::ExtraColumn { line: 2, column: 6 })
+        );
+        assert_eq!(
+            pairs("3 4\n\n1 2\n", ParseMode::Strict),
+            Err(InputError::MissingColumn { line: 2, found: 0 })
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_line_and_column() {
+        let error = pairs("3 4\n  1  x2\n", ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            error,
+            InputError::InvalidNumber {
+                line: 2,
+                column: 6,
+                token: "x2".to_string(),
+                reason: "invalid digit found in string".to_string()
+            }
+        );
+        assert_eq!(error.to_string(), "line 2, column 6: \"x2\" is not a valid integer (invalid digit found in string)");
+        assert_eq!(
+            pairs("3 4\n5 6\n7\t8\n1\n", ParseMode::Strict).unwrap_err().to_string(),
+            "line 4: expected 2 columns, found 1"
+        );
+    }
+
+    #[test]
+    fn lenient_mode_skips_irregular_lines() {
+        let input = "3 4\nname value\n5\n\n1 2 extra\n7 x\n";
+        assert_eq!(pairs(input, ParseMode::Lenient), Ok((vec![1, 3], vec![2, 4])));
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored() {
+        for mode in [ParseMode::Strict, ParseMode::Lenient] {
+            assert_eq!(pairs("3 4\n1 2\n\n \r\n", mode), Ok((vec![1, 3], vec![2, 4])));
+            assert_eq!(pairs("\n\n", mode), Ok((vec![], vec![])));
+        }
+    }
+
+    #[test]
+    fn lists_of_different_lengths_are_rejected() {
+        assert_eq!(
+            IntegerPairProcessor::new(vec![1, 2], vec![3]).err(),
+            Some(InputError::UnequalLengths { first: 2, second: 1 })
+        );
+    }
+}
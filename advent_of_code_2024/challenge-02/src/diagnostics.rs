@@ -0,0 +1,131 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::policy::Violation;
+
+/// A problem found in one line of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// A report that is not safe, even after the allowed removals
+    Unsafe {
+        line: usize,          // Line of the report in the input, from 1
+        levels: Vec<i32>,     // The levels of the report
+        violation: Violation, // The first pair of levels breaking a rule
+    },
+    /// A line that is not a list of integers
+    Malformed {
+        line: usize,    // Line in the input, from 1
+        text: String,   // The line as written
+        reason: String, // Why it could not be parsed
+    },
+}
+
+impl Diagnostic {
+    /// Line of the input the diagnostic is about, from 1
+    pub fn line(&self) -> usize {
+        match self {
+            Diagnostic::Unsafe { line, .. } | Diagnostic::Malformed { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Unsafe { line, levels, violation } => {
+                let levels: Vec<String> = levels.iter().map(|level| level.to_string()).collect();
+                write!(
+                    f,
+                    "line {}: unsafe [{}]: {} between {} and {} (index {})",
+                    line,
+                    levels.join(" "),
+                    violation.rule,
+                    violation.from,
+                    violation.to,
+                    violation.index
+                )
+            }
+            Diagnostic::Malformed { line, text, reason } => {
+                write!(f, "line {}: malformed {:?}: {}", line, text, reason)
+            }
+        }
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes the diagnostics as CSV, one row per diagnostic, with a header row.
+///
+/// Columns that do not apply to a kind of diagnostic are left empty.
+pub fn write_csv<W: Write>(mut writer: W, diagnostics: &[Diagnostic]) -> io::Result<()> {
+    writeln!(writer, "line,kind,levels,index,from,to,rule,text,reason")?;
+
+    for diagnostic in diagnostics {
+        match diagnostic {
+            Diagnostic::Unsafe { line, levels, violation } => {
+                let levels: Vec<String> = levels.iter().map(|level| level.to_string()).collect();
+                writeln!(
+                    writer,
+                    "{},unsafe,{},{},{},{},{},,",
+                    line,
+                    csv_field(&levels.join(" ")),
+                    violation.index,
+                    violation.from,
+                    violation.to,
+                    csv_field(&violation.rule.to_string())
+                )?;
+            }
+            Diagnostic::Malformed { line, text, reason } => {
+                writeln!(writer, "{},malformed,,,,,,{},{}", line, csv_field(text), csv_field(reason))?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Rule;
+
+    #[test]
+    fn fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("step too large"), "step too large");
+        assert_eq!(csv_field("1,2"), "\"1,2\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\r\nb"), "\"a\r\nb\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn csv_has_a_header_and_a_row_per_diagnostic() {
+        let diagnostics = [
+            Diagnostic::Unsafe {
+                line: 2,
+                levels: vec![1, 2, 7, 8, 9],
+                violation: Violation { index: 1, from: 2, to: 7, rule: Rule::StepTooLarge },
+            },
+            Diagnostic::Malformed { line: 3, text: "1,2 \"x\"".to_string(), reason: "bad, very bad".to_string() },
+        ];
+
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &diagnostics).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "line,kind,levels,index,from,to,rule,text,reason\n\
+             2,unsafe,1 2 7 8 9,1,2,7,step too large,,\n\
+             3,malformed,,,,,,\"1,2 \"\"x\"\"\",\"bad, very bad\"\n"
+        );
+
+        assert_eq!(diagnostics[0].to_string(), "line 2: unsafe [1 2 7 8 9]: step too large between 2 and 7 (index 1)");
+        assert_eq!(diagnostics[1].to_string(), "line 3: malformed \"1,2 \\\"x\\\"\": bad, very bad");
+    }
+}
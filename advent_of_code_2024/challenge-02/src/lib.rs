@@ -0,0 +1,162 @@
+pub mod diagnostics;
+pub mod policy;
+
+use aoc_common::parse::parse_numbers;
+use aoc_common::solution::{Answer, Error, Solution};
+
+pub use diagnostics::{write_csv, Diagnostic};
+pub use policy::{Rule, SafetyPolicy, Trend, Violation};
+
+/// Struct to represent a row of numbers in the report.
+struct Row {
+    line: usize,       // Line of the row in the input, from 1.
+    numbers: Vec<i32>, // List of numbers in the row.
+}
+
+/// A line of the input that is not a list of integers.
+struct Malformed {
+    line: usize,    // Line in the input, from 1.
+    text: String,   // The line as written.
+    reason: String, // Why it could not be parsed.
+}
+
+/// How many rows a policy considers safe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakdown {
+    pub policy: SafetyPolicy,    // The policy the rows were checked against
+    pub safe: usize,             // Rows that are safe, possibly after removals
+    pub unsafe_rows: usize,      // Rows that cannot be made safe
+    pub by_removals: Vec<usize>, // Safe rows by the number of levels dropped, from 0 to `policy.removals`
+}
+
+/// A processor that manages multiple rows of data and performs operations on them.
+pub struct Processor {
+    rows: Vec<Row>,            // A collection of rows.
+    malformed: Vec<Malformed>, // Lines that could not be parsed, left out of the rows.
+}
+
+impl Processor {
+    /// Checks every row against every policy.
+    pub fn count_safe(&self, policies: &[SafetyPolicy]) -> Vec<Breakdown> {
+        policies
+            .iter()
+            .map(|&policy| {
+                let mut by_removals = vec![0; policy.removals + 1];
+                for row in &self.rows {
+                    if let Some(removed) = policy.removals_needed(&row.numbers) {
+                        by_removals[removed.len()] += 1;
+                    }
+                }
+                let safe = by_removals.iter().sum();
+                Breakdown { policy, safe, unsafe_rows: self.rows.len() - safe, by_removals }
+            })
+            .collect()
+    }
+
+    /// Lists every row that is unsafe under `policy`, with the first pair of
+    /// levels breaking a rule, and every malformed line, ordered by line.
+    pub fn diagnose(&self, policy: SafetyPolicy) -> Vec<Diagnostic> {
+        let unsafe_rows = self.rows.iter().filter(|row| !policy.is_safe(&row.numbers)).filter_map(|row| {
+            policy.first_violation(&row.numbers).map(|violation| Diagnostic::Unsafe {
+                line: row.line,
+                levels: row.numbers.clone(),
+                violation,
+            })
+        });
+        let malformed = self.malformed.iter().map(|malformed| Diagnostic::Malformed {
+            line: malformed.line,
+            text: malformed.text.clone(),
+            reason: malformed.reason.clone(),
+        });
+
+        let mut diagnostics: Vec<Diagnostic> = unsafe_rows.chain(malformed).collect();
+        diagnostics.sort_by_key(Diagnostic::line);
+        diagnostics
+    }
+
+    /// Counts how many rows are safe under `policy`, failing on the first
+    /// malformed line so that no line is silently left out of the count.
+    fn count_safe_with(&self, policy: SafetyPolicy) -> Result<usize, Error> {
+        if let Some(malformed) = self.malformed.first() {
+            let more = match self.malformed.len() - 1 {
+                0 => String::new(),
+                more => format!(" (and {} more malformed lines)", more),
+            };
+            return Err(format!("line {}: malformed {:?}: {}{}", malformed.line, malformed.text, malformed.reason, more).into());
+        }
+
+        Ok(self.rows.iter().filter(|row| policy.is_safe(&row.numbers)).count())
+    }
+
+    /// Adds a new row to the processor's collection.
+    fn add_row(&mut self, row: Row) {
+        self.rows.push(row);
+    }
+}
+
+/// Solver for Day 2: Red-Nosed Reports
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    type Input = Processor;
+
+    fn parse(input: &str) -> Result<Processor, Error> {
+        let mut processor = Processor {
+            rows: Vec::new(),
+            malformed: Vec::new(),
+        };
+
+        // Process each line of the input
+        for (index, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Parse the line into a vector of integers; lines with a bad token are kept aside
+            match parse_numbers::<i32>(line) {
+                // Create a new Row struct and add it to the processor
+                Ok(numbers) => processor.add_row(Row { line: index + 1, numbers }),
+                Err(e) => processor.malformed.push(Malformed { line: index + 1, text: line.to_string(), reason: e.to_string() }),
+            }
+        }
+
+        Ok(processor)
+    }
+
+    fn part1(processor: &Processor) -> Result<Answer, Error> {
+        Ok(processor.count_safe_with(SafetyPolicy::strict())?.into())
+    }
+
+    fn part2(processor: &Processor) -> Result<Answer, Error> {
+        Ok(processor.count_safe_with(SafetyPolicy::dampened())?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_lines_fail_both_parts() {
+        let processor = Day02::parse("7 6 4 2 1\n1 2 x 4\n\n1 3 6 7 9\n9 y\n").unwrap();
+        let error = Day02::part1(&processor).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "line 2: malformed \"1 2 x 4\": \"x\" is not a valid number: invalid digit found in string (and 1 more malformed lines)"
+        );
+        assert!(Day02::part2(&processor).is_err());
+
+        // --diagnose still lists them
+        let lines: Vec<usize> = processor.diagnose(SafetyPolicy::dampened()).iter().map(Diagnostic::line).collect();
+        assert_eq!(lines, [2, 5]);
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        let processor = Day02::parse("7 6 4 2 1\n\n1 2 7 8 9\n").unwrap();
+        assert_eq!(Day02::part1(&processor).unwrap(), Answer::Number(1));
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+
+/// The direction the levels of a report must move in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    /// Every level is higher than the previous one
+    Increasing,
+    /// Every level is lower than the previous one
+    Decreasing,
+    /// Either direction, as long as it never changes
+    Either,
+}
+
+/// A rule a report can break
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// The report has fewer than two levels
+    TooShort,
+    /// Two adjacent levels are equal
+    EqualNeighbours,
+    /// Two adjacent levels are closer than the smallest allowed step
+    StepTooSmall,
+    /// Two adjacent levels are further apart than the largest allowed step
+    StepTooLarge,
+    /// The levels start moving in the opposite direction
+    DirectionChange,
+    /// The levels move in the direction the policy does not allow
+    WrongDirection,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Rule::TooShort => "too short",
+            Rule::EqualNeighbours => "equal neighbours",
+            Rule::StepTooSmall => "step too small",
+            Rule::StepTooLarge => "step too large",
+            Rule::DirectionChange => "direction change",
+            Rule::WrongDirection => "wrong direction",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// The first pair of adjacent levels that breaks a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub index: usize, // Index of the first level of the pair
+    pub from: i32,    // The first level of the pair
+    pub to: i32,      // The second level of the pair
+    pub rule: Rule,   // The rule the pair breaks
+}
+
+/// The rules a report must follow to be safe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    pub min_step: i32,   // Smallest allowed difference between two adjacent levels
+    pub max_step: i32,   // Largest allowed difference between two adjacent levels
+    pub trend: Trend,    // Direction the levels must move in
+    pub removals: usize, // Number of levels that may be dropped to make the report safe
+}
+
+impl SafetyPolicy {
+    /// The rules of part 1: steps of 1 to 3 in a single direction, no removals
+    pub fn strict() -> Self {
+        SafetyPolicy { min_step: 1, max_step: 3, trend: Trend::Either, removals: 0 }
+    }
+
+    /// The rules of part 2: like `strict`, but one level may be dropped
+    pub fn dampened() -> Self {
+        SafetyPolicy { removals: 1, ..SafetyPolicy::strict() }
+    }
+
+    /// The same rules, with up to `removals` levels that may be dropped
+    pub fn with_removals(self, removals: usize) -> Self {
+        SafetyPolicy { removals, ..self }
+    }
+
+    /// Checks if going from `from` to `to` is allowed when moving in `trend`
+    fn allows_step(&self, from: i32, to: i32, trend: Trend) -> bool {
+        // Widened so that steps between extreme levels cannot overflow
+        let (from, to) = (i64::from(from), i64::from(to));
+        let step = match trend {
+            Trend::Decreasing => from - to,
+            _ => to - from,
+        };
+        (i64::from(self.min_step)..=i64::from(self.max_step)).contains(&step)
+    }
+
+    /// Finds the first pair of adjacent levels that breaks the rules, without
+    /// dropping any level, or `None` if the report is safe as it is.
+    ///
+    /// A report with fewer than two levels breaks `Rule::TooShort` at index 0.
+    pub fn first_violation(&self, levels: &[i32]) -> Option<Violation> {
+        if levels.len() < 2 {
+            let level = levels.first().copied().unwrap_or(0);
+            return Some(Violation { index: 0, from: level, to: level, rule: Rule::TooShort });
+        }
+
+        // With `Trend::Either` the first pair decides the direction
+        let increasing = match self.trend {
+            Trend::Increasing => true,
+            Trend::Decreasing => false,
+            Trend::Either => levels[1] >= levels[0],
+        };
+
+        levels.windows(2).enumerate().find_map(|(index, pair)| {
+            let (from, to) = (pair[0], pair[1]);
+            let step = (i64::from(to) - i64::from(from)).abs();
+            let (min_step, max_step) = (i64::from(self.min_step), i64::from(self.max_step));
+            let rule = if step < min_step && from == to {
+                Rule::EqualNeighbours
+            } else if from != to && (to > from) != increasing {
+                match self.trend {
+                    Trend::Either => Rule::DirectionChange,
+                    _ => Rule::WrongDirection,
+                }
+            } else if step < min_step {
+                Rule::StepTooSmall
+            } else if step > max_step {
+                Rule::StepTooLarge
+            } else {
+                return None;
+            };
+            Some(Violation { index, from, to, rule })
+        })
+    }
+
+    /// Checks if `levels` are safe, possibly after dropping some of them
+    pub fn is_safe(&self, levels: &[i32]) -> bool {
+        self.removals_needed(levels).is_some()
+    }
+
+    /// Finds the fewest levels to drop to make `levels` safe.
+    ///
+    /// Returns the indices to drop (empty if the report is already safe), or
+    /// `None` if more than `removals` levels would have to go. At least two
+    /// levels must be left for a report to be safe.
+    pub fn removals_needed(&self, levels: &[i32]) -> Option<Vec<usize>> {
+        let mut chains = Vec::new();
+        match self.trend {
+            Trend::Either => {
+                let increasing = self.removals_needed_for(levels, Trend::Increasing, &mut chains);
+                let decreasing = self.removals_needed_for(levels, Trend::Decreasing, &mut chains);
+                match (increasing, decreasing) {
+                    (Some(a), Some(b)) if b.len() < a.len() => Some(b),
+                    (Some(a), _) => Some(a),
+                    (None, b) => b,
+                }
+            }
+            trend => self.removals_needed_for(levels, trend, &mut chains),
+        }
+    }
+
+    /// Finds the fewest levels to drop so that the others move in `trend`.
+    ///
+    /// A dynamic programme over the levels, which finds the longest chain of
+    /// kept levels ending at each level `i`. The longest chain also drops the
+    /// fewest levels before `i`, so it is the only one worth extending. The
+    /// level kept before `i` is one of the `k + 1` levels before it, as a wider
+    /// gap alone drops more than `k` levels, so the work is O(n·k) for `n` levels
+    /// and `k` removals. The chains are kept in a single buffer of `n` entries,
+    /// reused for every trend.
+    fn removals_needed_for(&self, levels: &[i32], trend: Trend, chains: &mut Vec<Chain>) -> Option<Vec<usize>> {
+        let n = levels.len();
+        if n < 2 {
+            return None;
+        }
+        // Two levels must be kept, so dropping more than `n - 2` never helps
+        let k = self.removals.min(n - 2);
+
+        chains.clear();
+        for i in 0..n {
+            // Start the kept levels at `i`, or keep `i` right after `j`, dropping the levels in between
+            let mut chain = Chain { length: 1, previous: None };
+            for j in i.saturating_sub(k + 1)..i {
+                if chains[j].length >= chain.length && self.allows_step(levels[j], levels[i], trend) {
+                    chain = Chain { length: chains[j].length + 1, previous: Some(j) };
+                }
+            }
+            chains.push(chain);
+        }
+
+        // End the kept levels at `last`, dropping every level after it; at least two must be kept
+        let last = (0..n).max_by_key(|&i| chains[i].length)?;
+        let kept_count = chains[last].length;
+        if kept_count < 2 || n - kept_count > k {
+            return None;
+        }
+
+        // Walk back through the kept levels; every index not on the path is dropped
+        let mut kept = vec![false; n];
+        let mut i = Some(last);
+        while let Some(index) = i {
+            kept[index] = true;
+            i = chains[index].previous;
+        }
+
+        Some((0..n).filter(|&index| !kept[index]).collect())
+    }
+}
+
+/// The longest chain of kept levels ending at a level, in `SafetyPolicy::removals_needed_for`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Chain {
+    length: usize,           // Number of levels kept, up to and including this one
+    previous: Option<usize>, // Index of the level kept right before this one, if any
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The fewest levels to drop to make `levels` safe, trying every subset
+    fn brute_force(policy: &SafetyPolicy, levels: &[i32]) -> Option<usize> {
+        let strict = policy.with_removals(0);
+        (0u32..1 << levels.len())
+            .filter(|dropped| dropped.count_ones() as usize <= policy.removals)
+            .filter(|dropped| {
+                let kept: Vec<i32> = (0..levels.len()).filter(|i| dropped & (1 << i) == 0).map(|i| levels[i]).collect();
+                strict.first_violation(&kept).is_none()
+            })
+            .map(|dropped| dropped.count_ones() as usize)
+            .min()
+    }
+
+    #[test]
+    fn removals_match_a_brute_force_search() {
+        // A small linear congruential generator, so the reports are always the same
+        let mut seed = 2024u64;
+        let mut next = |bound: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+
+        for _ in 0..2_000 {
+            let len = next(9) as usize;
+            let mut levels = vec![next(10) as i32];
+            for _ in 1..len {
+                levels.push(levels[levels.len() - 1] + next(9) as i32 - 4);
+            }
+
+            for removals in 0..=6 {
+                for trend in [Trend::Either, Trend::Increasing, Trend::Decreasing] {
+                    let (min_step, max_step) = if next(4) == 0 { (2, 4) } else { (1, 3) };
+                    let policy = SafetyPolicy { min_step, max_step, trend, removals };
+                    let dropped = policy.removals_needed(&levels);
+                    assert_eq!(dropped.as_ref().map(Vec::len), brute_force(&policy, &levels), "{:?} {:?}", policy, levels);
+
+                    // The levels left are safe as they are
+                    if let Some(dropped) = dropped {
+                        let kept: Vec<i32> = (0..levels.len()).filter(|i| !dropped.contains(i)).map(|i| levels[i]).collect();
+                        assert_eq!(policy.with_removals(0).first_violation(&kept), None);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn removals_are_bounded_by_the_length_of_the_report() {
+        let policy = SafetyPolicy::strict().with_removals(usize::MAX);
+        assert_eq!(policy.removals_needed(&[1, 9, 2, 8, 3]), Some(vec![1, 3]));
+        assert_eq!(policy.removals_needed(&[1]), None);
+    }
+
+    #[test]
+    fn extreme_levels_do_not_overflow() {
+        let policy = SafetyPolicy::dampened();
+        let levels = [i32::MIN, i32::MAX, i32::MAX - 1];
+        assert_eq!(
+            policy.first_violation(&levels),
+            Some(Violation { index: 0, from: i32::MIN, to: i32::MAX, rule: Rule::StepTooLarge })
+        );
+        assert_eq!(policy.removals_needed(&levels), Some(vec![0]));
+        assert_eq!(policy.removals_needed(&[i32::MAX, i32::MIN, 0]), None);
+        assert!(SafetyPolicy { min_step: i32::MIN, max_step: i32::MAX, ..policy }.is_safe(&[i32::MAX, i32::MIN, -5]));
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+/// Longest operand accepted by the puzzle, in digits
+pub const MAX_OPERAND_DIGITS: usize = 3;
+
+/// The state instructions act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    pub enabled: bool, // Whether conditional instructions currently run
+    pub total: i64,    // Sum of the results of the instructions executed so far
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Machine { enabled: true, total: 0 }
+    }
+}
+
+/// An instruction of the corrupted memory language, written `name(a,b,...)`
+/// with exactly `arity` operands of 1 to 3 digits each.
+#[derive(Clone)]
+pub struct Instruction {
+    pub name: String,                      // Name written before the parentheses
+    pub arity: usize,                      // Number of operands
+    pub conditional: bool,                 // Whether it is skipped while the machine is disabled
+    pub execute: fn(&mut Machine, &[i64]), // What it does to the machine
+}
+
+impl Instruction {
+    /// Creates an instruction that only runs while the machine is enabled
+    pub fn new(name: &str, arity: usize, execute: fn(&mut Machine, &[i64])) -> Self {
+        Instruction { name: name.to_string(), arity, conditional: true, execute }
+    }
+
+    /// Creates an instruction that runs even while the machine is disabled
+    pub fn unconditional(name: &str, arity: usize, execute: fn(&mut Machine, &[i64])) -> Self {
+        Instruction { conditional: false, ..Instruction::new(name, arity, execute) }
+    }
+
+    /// `mul(a,b)`: adds `a * b` to the total
+    pub fn mul() -> Self {
+        Instruction::new("mul", 2, |machine, operands| machine.total += operands[0] * operands[1])
+    }
+
+    /// `do()`: enables the conditional instructions that follow
+    pub fn enable() -> Self {
+        Instruction::unconditional("do", 0, |machine, _| machine.enabled = true)
+    }
+
+    /// `don't()`: disables the conditional instructions that follow
+    pub fn disable() -> Self {
+        Instruction::unconditional("don't", 0, |machine, _| machine.enabled = false)
+    }
+
+    /// Length of the longest valid instance of the instruction, in bytes
+    pub fn max_len(&self) -> usize {
+        // Name, parentheses, operands and the commas between them
+        self.name.len() + 2 + self.arity * MAX_OPERAND_DIGITS + self.arity.saturating_sub(1)
+    }
+}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instruction")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .field("conditional", &self.conditional)
+            .finish()
+    }
+}
+
+/// The instructions recognised in the corrupted memory; anything else is noise
+#[derive(Debug, Clone, Default)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+}
+
+impl InstructionSet {
+    /// Creates a set with no instructions
+    pub fn new() -> Self {
+        InstructionSet::default()
+    }
+
+    /// The instructions of part 1: only `mul`
+    pub fn part1() -> Self {
+        InstructionSet::new().with(Instruction::mul())
+    }
+
+    /// The instructions of part 2: `mul`, `do` and `don't`
+    pub fn part2() -> Self {
+        InstructionSet::part1().with(Instruction::enable()).with(Instruction::disable())
+    }
+
+    /// Adds an instruction to the set, returning the set
+    pub fn with(mut self, instruction: Instruction) -> Self {
+        self.register(instruction);
+        self
+    }
+
+    /// Adds an instruction to the set
+    pub fn register(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+
+    /// The instruction at `index`, in registration order
+    pub fn get(&self, index: usize) -> &Instruction {
+        &self.instructions[index]
+    }
+
+    /// Iterates over the instructions, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &Instruction> {
+        self.instructions.iter()
+    }
+
+    /// Length of the longest valid instruction of the set, in bytes
+    pub fn max_len(&self) -> usize {
+        self.iter().map(Instruction::max_len).max().unwrap_or(0)
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::io::{self, Read};
+
+use crate::instruction::{InstructionSet, Machine};
+use crate::lexer::{Lexer, Token};
+
+/// One instruction met while running, for the trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub offset: usize,  // Byte offset of the instruction in the input
+    pub text: String,   // The instruction as written, e.g. `mul(2,4)`
+    pub executed: bool, // False if it was skipped because the machine was disabled
+    pub total: i64,     // Total right after the instruction
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.executed { "executed" } else { "skipped" };
+        write!(f, "{:>10}  {:<8}  {:<16}  total = {}", self.offset, status, self.text, self.total)
+    }
+}
+
+/// Size of the chunks read by `run_reader` by default, in bytes
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// What an interpreter does with every instruction it meets
+enum Trace<'s> {
+    Off,                               // Nothing
+    Record(Vec<Step>),                 // Keep them all, to be read back with `Interpreter::trace`
+    Report(Box<dyn FnMut(Step) + 's>), // Hand each one over as soon as it runs
+}
+
+/// Runs the instructions found in corrupted memory
+pub struct Interpreter<'s> {
+    instructions: &'s InstructionSet, // Instructions to recognise and run
+    machine: Machine,                 // State after the instructions run so far
+    trace: Trace<'s>,                 // What to do with every instruction met
+    pending: Vec<u8>,                 // Streamed bytes that may start an instruction cut off by the end of a chunk
+    base: usize,                      // Offset of `pending` in the whole input
+}
+
+impl<'s> Interpreter<'s> {
+    /// Creates an interpreter for `instructions`, with the machine enabled and a zero total
+    pub fn new(instructions: &'s InstructionSet) -> Self {
+        Interpreter { instructions, machine: Machine::default(), trace: Trace::Off, pending: Vec::new(), base: 0 }
+    }
+
+    /// Records every executed and skipped instruction
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Trace::Record(Vec::new());
+        self
+    }
+
+    /// Hands every executed and skipped instruction to `report` as soon as it
+    /// runs, instead of recording it, so that tracing a streamed text does not
+    /// keep the whole trace in memory
+    pub fn with_trace_to(mut self, report: impl FnMut(Step) + 's) -> Self {
+        self.trace = Trace::Report(Box::new(report));
+        self
+    }
+
+    /// Runs every instruction of a complete text
+    pub fn run(&mut self, text: &[u8]) {
+        let instructions = self.instructions;
+        Lexer::new(instructions).lex(text, 0, true, |token| {
+            let written = &text[token.offset..token.offset + token.len];
+            self.execute(token, written);
+        });
+    }
+
+    /// Streams the text from `reader` in chunks of `chunk_size` bytes and runs
+    /// every instruction in it, so the input never has to fit in memory.
+    pub fn run_reader<R: Read>(&mut self, reader: R, chunk_size: usize) -> io::Result<()> {
+        run_reader_all(reader, chunk_size, std::slice::from_mut(self))
+    }
+
+    /// Runs every instruction of the next chunk of a streamed text.
+    ///
+    /// An instruction cut off by the end of the chunk is kept, together with
+    /// what follows it, until the next chunk arrives; since it is at most a
+    /// few bytes long, memory use stays bounded by the chunk size.
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.pending.extend_from_slice(chunk);
+        self.lex_pending(false);
+    }
+
+    /// Ends a streamed text, running what is left of it
+    pub fn finish(&mut self) {
+        self.lex_pending(true);
+    }
+
+    /// Runs the instructions of the bytes streamed so far; unless `last`,
+    /// keeps those that may start an instruction cut off by the end of the chunk
+    fn lex_pending(&mut self, last: bool) {
+        let mut pending = std::mem::take(&mut self.pending);
+        let base = self.base;
+
+        let stop = Lexer::new(self.instructions).lex(&pending, base, last, |token| {
+            let start = token.offset - base;
+            let written = &pending[start..start + token.len];
+            self.execute(token, written);
+        });
+
+        pending.drain(..stop);
+        self.pending = pending;
+        self.base += stop;
+    }
+
+    /// Runs one instruction, unless it is conditional and the machine is disabled.
+    /// `written` is the instruction as it appears in the input.
+    fn execute(&mut self, token: Token, written: &[u8]) {
+        let instruction = self.instructions.get(token.instruction);
+        let executed = self.machine.enabled || !instruction.conditional;
+        if executed {
+            (instruction.execute)(&mut self.machine, &token.operands);
+        }
+
+        let step = || Step {
+            offset: token.offset,
+            text: String::from_utf8_lossy(written).into_owned(),
+            executed,
+            total: self.machine.total,
+        };
+        match &mut self.trace {
+            Trace::Off => {}
+            Trace::Record(steps) => steps.push(step()),
+            Trace::Report(report) => report(step()),
+        }
+    }
+
+    /// The state of the machine after the instructions run so far
+    pub fn machine(&self) -> Machine {
+        self.machine
+    }
+
+    /// Sum of the results of the instructions executed so far
+    pub fn total(&self) -> i64 {
+        self.machine.total
+    }
+
+    /// Every instruction met so far, or nothing unless recorded with `with_trace`
+    pub fn trace(&self) -> &[Step] {
+        match &self.trace {
+            Trace::Record(steps) => steps,
+            Trace::Off | Trace::Report(_) => &[],
+        }
+    }
+}
+
+/// Streams the text from `reader` in chunks of `chunk_size` bytes and feeds
+/// every chunk to each of the `interpreters`, so that they all run over the
+/// same input with a single pass over it.
+pub fn run_reader_all<R: Read>(mut reader: R, chunk_size: usize, interpreters: &mut [Interpreter<'_>]) -> io::Result<()> {
+    let mut chunk = vec![0; chunk_size.max(1)];
+
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if read == 0 {
+            interpreters.iter_mut().for_each(Interpreter::finish);
+            return Ok(());
+        }
+        for interpreter in interpreters.iter_mut() {
+            interpreter.feed(&chunk[..read]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY: &[u8] = b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn every_chunk_size_gives_the_same_trace() {
+        let instructions = InstructionSet::part2();
+        let mut whole = Interpreter::new(&instructions).with_trace();
+        whole.run(MEMORY);
+        assert_eq!(whole.total(), 48);
+
+        for chunk_size in 1..=MEMORY.len() + 1 {
+            let mut streamed = Interpreter::new(&instructions).with_trace();
+            streamed.run_reader(MEMORY, chunk_size).unwrap();
+            assert_eq!(streamed.trace(), whole.trace(), "chunks of {} bytes", chunk_size);
+            assert_eq!(streamed.machine(), whole.machine());
+        }
+    }
+
+    #[test]
+    fn reported_steps_match_the_recorded_trace() {
+        let instructions = InstructionSet::part2();
+        let mut whole = Interpreter::new(&instructions).with_trace();
+        whole.run(MEMORY);
+
+        let mut reported = Vec::new();
+        let mut streamed = Interpreter::new(&instructions).with_trace_to(|step| reported.push(step));
+        streamed.run_reader(MEMORY, 5).unwrap();
+        assert!(streamed.trace().is_empty());
+        assert_eq!(streamed.total(), 48);
+        drop(streamed);
+
+        assert_eq!(reported, whole.trace());
+    }
+
+    #[test]
+    fn state_carries_over_between_fed_chunks() {
+        let instructions = InstructionSet::part2();
+        let mut interpreter = Interpreter::new(&instructions).with_trace();
+        for chunk in [&b"mul(2,"[..], b"3)don", b"'t()mu", b"l(4,4)do(", b")mul(1,1", b")mul(9"] {
+            interpreter.feed(chunk);
+        }
+        interpreter.finish();
+
+        let offsets: Vec<(usize, bool)> = interpreter.trace().iter().map(|step| (step.offset, step.executed)).collect();
+        assert_eq!(offsets, [(0, true), (8, true), (15, false), (23, true), (27, true)]);
+        assert_eq!(interpreter.total(), 7);
+    }
+
+    #[test]
+    fn interpreters_share_one_pass_over_the_input() {
+        let (part1, part2) = (InstructionSet::part1(), InstructionSet::part2());
+        let mut interpreters = [Interpreter::new(&part1), Interpreter::new(&part2)];
+        run_reader_all(MEMORY, 7, &mut interpreters).unwrap();
+        assert_eq!(interpreters.map(|interpreter| interpreter.total()), [161, 48]);
+    }
+}
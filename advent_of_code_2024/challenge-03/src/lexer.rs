@@ -0,0 +1,206 @@
+use crate::instruction::{InstructionSet, MAX_OPERAND_DIGITS};
+
+/// A valid instruction found in the corrupted memory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub offset: usize,      // Byte offset of the instruction's first byte
+    pub len: usize,         // Length of the instruction, in bytes
+    pub instruction: usize, // Index of the instruction in its set
+    pub operands: Vec<i64>, // The operands, in order
+}
+
+/// What was found at one position of the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lexeme {
+    /// A valid instruction starts here
+    Instruction(Token),
+    /// No instruction starts here
+    Noise,
+    /// The text ends before it can be told whether an instruction starts here
+    Incomplete,
+}
+
+/// Result of matching one instruction against the text
+enum Attempt {
+    Match { len: usize, operands: Vec<i64> },
+    Mismatch,
+    Incomplete,
+}
+
+/// Matches `name(a,b,...)` with `arity` operands of 1 to 3 digits at the start of `text`
+fn match_instruction(text: &[u8], name: &[u8], arity: usize) -> Attempt {
+    let mut cursor = Cursor { text, at: 0 };
+
+    for &expected in name.iter().chain(b"(") {
+        match cursor.next() {
+            None => return Attempt::Incomplete,
+            Some(byte) if byte != expected => return Attempt::Mismatch,
+            Some(_) => {}
+        }
+    }
+
+    let mut operands = Vec::with_capacity(arity);
+    for index in 0..arity {
+        let mut operand = 0;
+        let mut digits = 0;
+        let separator = loop {
+            match cursor.next() {
+                None => return Attempt::Incomplete,
+                Some(byte @ b'0'..=b'9') if digits < MAX_OPERAND_DIGITS => {
+                    operand = operand * 10 + (byte - b'0') as i64;
+                    digits += 1;
+                }
+                Some(byte) => break byte,
+            }
+        };
+
+        // Every operand has 1 to 3 digits and is followed by ',' or, for the last one, ')'
+        let expected = if index + 1 == arity { b')' } else { b',' };
+        if digits == 0 || separator != expected {
+            return Attempt::Mismatch;
+        }
+        operands.push(operand);
+    }
+
+    if arity == 0 {
+        match cursor.next() {
+            None => return Attempt::Incomplete,
+            Some(b')') => {}
+            Some(_) => return Attempt::Mismatch,
+        }
+    }
+
+    Attempt::Match { len: cursor.at, operands }
+}
+
+/// Reads a byte slice one byte at a time
+struct Cursor<'a> {
+    text: &'a [u8],
+    at: usize,
+}
+
+impl Cursor<'_> {
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.text.get(self.at).copied();
+        self.at += 1;
+        byte
+    }
+}
+
+/// Splits corrupted memory into the instructions of a set, skipping
+/// everything else.
+pub struct Lexer<'s> {
+    instructions: &'s InstructionSet,
+}
+
+impl<'s> Lexer<'s> {
+    /// Creates a lexer recognising the instructions of `instructions`
+    pub fn new(instructions: &'s InstructionSet) -> Self {
+        Lexer { instructions }
+    }
+
+    /// Tells what starts at `at` in `text`. `base` is the offset of `text`
+    /// in the whole input, used for the offsets of the tokens.
+    pub fn lex_at(&self, text: &[u8], at: usize, base: usize) -> Lexeme {
+        let mut incomplete = false;
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            match match_instruction(&text[at..], instruction.name.as_bytes(), instruction.arity) {
+                // Names are followed by '(', so at most one instruction can match
+                Attempt::Match { len, operands } => {
+                    return Lexeme::Instruction(Token { offset: base + at, len, instruction: index, operands });
+                }
+                Attempt::Incomplete => incomplete = true,
+                Attempt::Mismatch => {}
+            }
+        }
+
+        if incomplete {
+            Lexeme::Incomplete
+        } else {
+            Lexeme::Noise
+        }
+    }
+
+    /// Lexes `text` from `at` onwards, calling `found` for every instruction.
+    ///
+    /// Returns where lexing stopped: the end of `text`, or, unless `last` says
+    /// no more text follows, the first position where an instruction may
+    /// start but is cut off by the end of `text`.
+    pub fn lex(&self, text: &[u8], base: usize, last: bool, mut found: impl FnMut(Token)) -> usize {
+        let mut at = 0;
+
+        while at < text.len() {
+            match self.lex_at(text, at, base) {
+                Lexeme::Instruction(token) => {
+                    at += token.len;
+                    found(token);
+                }
+                Lexeme::Incomplete if !last => return at,
+                Lexeme::Noise | Lexeme::Incomplete => at += 1,
+            }
+        }
+
+        at
+    }
+
+    /// Collects every instruction of a complete text
+    pub fn tokens(&self, text: &[u8]) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        self.lex(text, 0, true, |token| tokens.push(token));
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY: &[u8] = b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn finds_the_instructions_and_their_offsets() {
+        let instructions = InstructionSet::part2();
+        let tokens = Lexer::new(&instructions).tokens(MEMORY);
+        let found: Vec<(usize, usize, &[i64])> =
+            tokens.iter().map(|token| (token.offset, token.instruction, token.operands.as_slice())).collect();
+        assert_eq!(
+            found,
+            [(1, 0, &[2, 4][..]), (20, 2, &[]), (28, 0, &[5, 5]), (48, 0, &[11, 8]), (59, 1, &[]), (64, 0, &[8, 5])]
+        );
+    }
+
+    #[test]
+    fn operands_have_at_most_three_digits() {
+        let instructions = InstructionSet::part1();
+        let lexer = Lexer::new(&instructions);
+        assert_eq!(lexer.tokens(b"mul(999,1)").len(), 1);
+        assert!(lexer.tokens(b"mul(1000,1)mul(,1)mul(1,2 )").is_empty());
+    }
+
+    #[test]
+    fn an_instruction_cut_off_by_the_end_of_the_text_is_incomplete() {
+        let instructions = InstructionSet::part2();
+        let lexer = Lexer::new(&instructions);
+        for prefix in ["m", "mul", "mul(", "mul(12", "mul(12,", "mul(12,345", "do", "don't("] {
+            assert_eq!(lexer.lex_at(prefix.as_bytes(), 0, 0), Lexeme::Incomplete, "{:?}", prefix);
+        }
+        assert_eq!(lexer.lex_at(b"mux(", 0, 0), Lexeme::Noise);
+        assert_eq!(lexer.lex_at(b"mul(1234", 0, 0), Lexeme::Noise);
+    }
+
+    #[test]
+    fn lexing_stops_where_an_instruction_may_be_cut_off() {
+        let instructions = InstructionSet::part1();
+        let lexer = Lexer::new(&instructions);
+        let mut tokens = Vec::new();
+
+        // More text follows, so the trailing `mul(3,` is kept for later
+        let stop = lexer.lex(b"mul(1,2)xymul(3,", 100, false, |token| tokens.push(token.offset));
+        assert_eq!((stop, tokens.as_slice()), (10, &[100][..]));
+
+        // Nothing follows, so it is noise
+        let stop = lexer.lex(b"mul(1,2)xymul(3,", 100, true, |token| tokens.push(token.offset));
+        assert_eq!((stop, tokens.as_slice()), (16, &[100, 100][..]));
+    }
+}
@@ -0,0 +1,42 @@
+pub mod instruction;
+pub mod interpreter;
+pub mod lexer;
+
+use aoc_common::solution::{Answer, Error, Solution};
+
+pub use instruction::{Instruction, InstructionSet, Machine};
+pub use interpreter::{run_reader_all, Interpreter, Step, DEFAULT_CHUNK_SIZE};
+pub use lexer::{Lexeme, Lexer, Token};
+
+/// Runs the instructions of `instructions` found in the corrupted memory and
+/// returns the sum of their results.
+///
+/// Part 1 only knows `mul`; part 2 also knows `do()` and `don't()`, which
+/// enable and disable the multiplications that follow them.
+pub fn run(contents: &str, instructions: &InstructionSet) -> i64 {
+    let mut interpreter = Interpreter::new(instructions);
+    interpreter.run(contents.as_bytes());
+    interpreter.total()
+}
+
+/// Solver for Day 3: Mull It Over
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(contents: &String) -> Result<Answer, Error> {
+        Ok(run(contents, &InstructionSet::part1()).into())
+    }
+
+    fn part2(contents: &String) -> Result<Answer, Error> {
+        Ok(run(contents, &InstructionSet::part2()).into())
+    }
+}
@@ -1,57 +1,76 @@
 use std::fs::File;
-use std::io::{self, Read};
-use regex::Regex;
-
-fn main() -> io::Result<()> {
-
-    // Open the file `puzzle_input.txt` and handle potential errors
-    let mut file = File::open("puzzle_input.txt")?;
-
-    // Initialize a string to store the file's contents
-    let mut contents = String::new();
-
-    // Read the contents of the file into the string
-    file.read_to_string(&mut contents)?;
-
-    // Define a regular expression pattern to match specific function calls:
-    // "mul(<num>,<num>)", "do()", and "don't()"
-    let pattern = r"mul\(\d+,\d+\)|do\(\)|don't\(\)";
-
-    // Compile the regular expression
-    let re = Regex::new(pattern).unwrap();
-
-    let mut res = 0;
-
-    // Flag to track whether multiplication should be performed
-    let mut flag = true;
-
-    // Iterate over all regex captures in the input string
-    for capture in re.captures_iter(&contents) {
-        
-        // Extract the matched substring from the capture
-        let match_str = capture.get(0).unwrap().as_str();
-        
-        if match_str == "do()" {
-            flag = true; // Enable multiplication flag
-        } else if match_str == "don't()" {
-            flag = false; // Disable multiplication flag
-        } else if match_str.starts_with("mul(") {
-            if flag {
-                // Extract the numbers from the "mul(<x>,<y>)" format
-                let nums = &match_str[4..match_str.len() - 1]; // Remove "mul(" and ")"
-
-                // Split the string into parts (x and y)
-                let parts: Vec<&str> = nums.split(',').collect();
-
-                // Parse the individual parts into integers
-                let x: i32 = parts[0].parse().unwrap();
-                let y: i32 = parts[1].parse().unwrap();
-                res += x * y;
-            }
-        }
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_common::input::DEFAULT_INPUT;
+use aoc_common::solution::{exit_code, run_main, Error};
+use challenge_03::{run_reader_all, Day03, InstructionSet, Interpreter, DEFAULT_CHUNK_SIZE};
+use clap::Parser;
+
+/// Solves day 3, or traces every instruction found in the corrupted memory.
+#[derive(Parser)]
+#[command(name = "challenge-03")]
+struct Cli {
+    /// Puzzle input file
+    #[arg(default_value = DEFAULT_INPUT)]
+    input: PathBuf,
+
+    /// List every executed and skipped instruction with its byte offset
+    #[arg(long)]
+    trace: bool,
+
+    /// Instructions to trace with: 1 for `mul` only, 2 to add `do` and `don't`
+    #[arg(long, default_value_t = 2, requires = "trace", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Read the input in chunks instead of loading it whole, for inputs that do not fit in memory
+    #[arg(long)]
+    stream: bool,
+
+    /// Size of the chunks read with --stream or --trace, in bytes
+    #[arg(long, default_value_t = DEFAULT_CHUNK_SIZE)]
+    chunk_size: usize,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if !cli.trace && !cli.stream {
+        return run_main::<Day03>(&cli.input);
     }
 
-    println!("{}", res);
+    exit_code(if cli.trace { trace(&cli) } else { stream(&cli) })
+}
+
+/// Runs `interpreters` over the input, read chunk by chunk
+fn run_streaming(cli: &Cli, interpreters: &mut [Interpreter]) -> Result<(), Error> {
+    let file = File::open(&cli.input).map_err(|e| format!("cannot read {}: {}", cli.input.display(), e))?;
+    run_reader_all(file, cli.chunk_size, interpreters)?;
+    Ok(())
+}
+
+/// Prints the answer to both parts, streaming the input once for both
+fn stream(cli: &Cli) -> Result<(), Error> {
+    let (part1, part2) = (InstructionSet::part1(), InstructionSet::part2());
+    let mut interpreters = [Interpreter::new(&part1), Interpreter::new(&part2)];
+    run_streaming(cli, &mut interpreters)?;
+
+    for (part, interpreter) in (1..).zip(&interpreters) {
+        println!("part {}: {}", part, interpreter.total());
+    }
+    Ok(())
+}
+
+/// Prints every instruction met with the instructions of the selected part
+fn trace(cli: &Cli) -> Result<(), Error> {
+    let instructions = match cli.part {
+        1 => InstructionSet::part1(),
+        _ => InstructionSet::part2(),
+    };
+
+    // Every step is printed as soon as it runs, so the trace never builds up in memory
+    let mut interpreter = Interpreter::new(&instructions).with_trace_to(|step| println!("{}", step));
+    run_streaming(cli, std::slice::from_mut(&mut interpreter))?;
+    println!("Total: {}", interpreter.total());
 
     Ok(())
 }
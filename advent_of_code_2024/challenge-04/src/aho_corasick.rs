@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+
+/// Marker for "no state" in the tables below
+const NONE: u32 = u32::MAX;
+
+/// An Aho–Corasick automaton: finds every occurrence of a set of patterns in
+/// a text with a single pass over it, whatever the number of patterns.
+///
+/// Bytes that appear in no pattern share a single class, so the transition
+/// table has one column per distinct pattern byte (plus one) instead of 256.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    classes: [u16; 256],      // Column of every byte in the transition table
+    class_count: usize,       // Number of columns in the transition table
+    transitions: Vec<u32>,    // Next state, indexed by `state * class_count + class`
+    outputs: Vec<Vec<usize>>, // Patterns ending exactly at every state
+    dict_links: Vec<u32>,     // Closest state on the failure chain with outputs, or NONE
+    lengths: Vec<usize>,      // Length of every pattern
+}
+
+impl AhoCorasick {
+    /// Builds the automaton for `patterns`. Empty patterns never match.
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        // Class 0 is for bytes that appear in no pattern
+        let mut classes = [0u16; 256];
+        let mut class_count = 1;
+        for pattern in patterns {
+            for &byte in pattern.as_ref() {
+                if classes[byte as usize] == 0 {
+                    classes[byte as usize] = class_count as u16;
+                    class_count += 1;
+                }
+            }
+        }
+
+        let mut automaton = AhoCorasick {
+            classes,
+            class_count,
+            transitions: vec![NONE; class_count],
+            outputs: vec![Vec::new()],
+            dict_links: vec![NONE],
+            lengths: patterns.iter().map(|pattern| pattern.as_ref().len()).collect(),
+        };
+        for (index, pattern) in patterns.iter().enumerate() {
+            automaton.insert(index, pattern.as_ref());
+        }
+        automaton.link();
+        automaton
+    }
+
+    /// Adds a pattern to the trie
+    fn insert(&mut self, index: usize, pattern: &[u8]) {
+        if pattern.is_empty() {
+            return;
+        }
+
+        let mut state = 0;
+        for &byte in pattern {
+            let slot = state * self.class_count + self.classes[byte as usize] as usize;
+            if self.transitions[slot] == NONE {
+                self.transitions[slot] = self.outputs.len() as u32;
+                self.transitions.extend(std::iter::repeat_n(NONE, self.class_count));
+                self.outputs.push(Vec::new());
+                self.dict_links.push(NONE);
+            }
+            state = self.transitions[slot] as usize;
+        }
+        self.outputs[state].push(index);
+    }
+
+    /// Turns the trie into a complete automaton, visiting states breadth first
+    /// so that the failure state of every state is ready before its children
+    fn link(&mut self) {
+        let mut fail = vec![0u32; self.outputs.len()];
+        let mut queue = VecDeque::new();
+
+        // Missing transitions from the root loop back to it
+        for class in 0..self.class_count {
+            match self.transitions[class] {
+                NONE => self.transitions[class] = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let fallback = fail[state] as usize;
+            for class in 0..self.class_count {
+                let slot = state * self.class_count + class;
+                let next_of_fallback = self.transitions[fallback * self.class_count + class];
+
+                match self.transitions[slot] {
+                    // No child: follow the failure state instead
+                    NONE => self.transitions[slot] = next_of_fallback,
+                    child => {
+                        let child = child as usize;
+                        fail[child] = next_of_fallback;
+                        let target = next_of_fallback as usize;
+                        self.dict_links[child] = if self.outputs[target].is_empty() {
+                            self.dict_links[target]
+                        } else {
+                            target as u32
+                        };
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Length of the pattern at `index`
+    pub fn pattern_len(&self, index: usize) -> usize {
+        self.lengths[index]
+    }
+
+    /// Calls `found(pattern, end)` for every occurrence of every pattern in
+    /// `text`, overlapping ones included, where `end` is the index right
+    /// after the occurrence. Occurrences are reported by increasing `end`.
+    pub fn for_each_match(&self, text: &[u8], mut found: impl FnMut(usize, usize)) {
+        let mut state = 0;
+        for (index, &byte) in text.iter().enumerate() {
+            state = self.transitions[state * self.class_count + self.classes[byte as usize] as usize] as usize;
+
+            // Report the patterns ending here, following the chain of shorter suffixes
+            let mut output = if self.outputs[state].is_empty() { self.dict_links[state] } else { state as u32 };
+            while output != NONE {
+                for &pattern in &self.outputs[output as usize] {
+                    found(pattern, index + 1);
+                }
+                output = self.dict_links[output as usize];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every `(pattern, end)` found by comparing each pattern at every offset
+    fn naive_matches(patterns: &[&str], text: &str) -> Vec<(usize, usize)> {
+        let mut matches: Vec<(usize, usize)> = (1..=text.len())
+            .flat_map(|end| {
+                patterns
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, pattern)| !pattern.is_empty() && text[..end].ends_with(*pattern))
+                    .map(move |(index, _)| (index, end))
+            })
+            .collect();
+        matches.sort_unstable();
+        matches
+    }
+
+    fn matches(patterns: &[&str], text: &str) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        AhoCorasick::new(patterns).for_each_match(text.as_bytes(), |pattern, end| matches.push((pattern, end)));
+        matches.sort_unstable();
+        matches
+    }
+
+    #[test]
+    fn finds_overlapping_and_nested_patterns() {
+        let patterns = ["he", "she", "his", "hers", "e", ""];
+        let text = "ushershehishe";
+        assert_eq!(matches(&patterns, text), naive_matches(&patterns, text));
+        assert_eq!(matches(&["aa", "aaa"], "aaaa"), [(0, 2), (0, 3), (0, 4), (1, 3), (1, 4)]);
+    }
+
+    #[test]
+    fn reports_duplicate_patterns_separately() {
+        assert_eq!(matches(&["XMAS", "SAMX", "XMAS"], "XMASAMX"), [(0, 4), (1, 7), (2, 4)]);
+    }
+
+    #[test]
+    fn bytes_outside_the_patterns_reset_the_search() {
+        assert_eq!(matches(&["XMAS"], "XMA.XMAS"), [(0, 8)]);
+        assert!(matches(&["XMAS"], "").is_empty());
+        assert!(matches(&[] as &[&str], "XMAS").is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+pub mod aho_corasick;
+pub mod scanner;
+pub mod word_search;
+
+use aoc_common::solution::{Answer, Error, Solution};
+use aoc_common::Grid;
+
+pub use scanner::{grid_lines, GridLine, Scanner};
+pub use word_search::{find_templates, find_words, DirectionSet, Template, TemplateMatch, WordMatch};
+
+/// The word to look for in part 1
+const XMAS: &str = "XMAS";
+
+/// Two "MAS" crossing in the shape of an X, in one of its four orientations
+const X_MAS: &str = "\
+M.S
+.A.
+M.S";
+
+/// Solver for Day 4: Ceres Search
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, Error> {
+        Ok(Grid::parse(input, |c| c as u8)?)
+    }
+
+    fn part1(grid: &Grid<u8>) -> Result<Answer, Error> {
+        // Count "XMAS" read in any of the 8 directions
+        Ok(Scanner::new(&[XMAS], DirectionSet::All).scan(grid).len().into())
+    }
+
+    fn part2(grid: &Grid<u8>) -> Result<Answer, Error> {
+        // Count the X-MAS crosses, turned in any of the 4 ways
+        let templates = Template::parse(X_MAS).rotations();
+        Ok(find_templates(grid, &templates).len().into())
+    }
+}
@@ -0,0 +1,145 @@
+use aoc_common::{Direction, Grid, Position};
+
+use crate::aho_corasick::AhoCorasick;
+use crate::word_search::{DirectionSet, WordMatch};
+
+/// A straight run of cells across the grid, from one edge to the other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridLine {
+    pub start: Position,      // First cell of the line
+    pub direction: Direction, // Direction the line runs in
+    pub bytes: Vec<u8>,       // Letters of the line, in order
+}
+
+/// The directions lines are extracted in; the opposite directions are
+/// covered by searching for the reversed words
+fn line_directions(directions: DirectionSet) -> &'static [Direction] {
+    match directions {
+        DirectionSet::Orthogonal => &[Direction::Right, Direction::Down],
+        DirectionSet::Diagonal => &[Direction::DownRight, Direction::DownLeft],
+        DirectionSet::All => &[Direction::Right, Direction::Down, Direction::DownRight, Direction::DownLeft],
+    }
+}
+
+/// Extracts every row, column and/or diagonal of the grid, depending on
+/// `directions`, so that every cell appears once per line direction
+pub fn grid_lines(grid: &Grid<u8>, directions: DirectionSet) -> Vec<GridLine> {
+    let mut lines = Vec::new();
+
+    for &direction in line_directions(directions) {
+        // A line starts at every cell whose predecessor is off the grid
+        let starts = grid.positions().filter(|&position| !grid.in_bounds(position - direction.offset()));
+        for start in starts {
+            let mut bytes = Vec::new();
+            let mut position = start;
+            while let Some(&cell) = grid.get(position) {
+                bytes.push(cell);
+                position = position.step(direction);
+            }
+            lines.push(GridLine { start, direction, bytes });
+        }
+    }
+
+    lines
+}
+
+/// Finds a whole dictionary of words in a grid with one pass over every line.
+///
+/// Every word is searched as-is and reversed, so that reading the lines in
+/// one direction also finds the words written in the opposite one.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    automaton: AhoCorasick,       // Matches the words and their reverses
+    patterns: Vec<(usize, bool)>, // Word index of every pattern, and whether it is reversed
+    directions: DirectionSet,     // Directions the words can be read in
+}
+
+impl Scanner {
+    /// Builds a scanner for `words`, read in any of the given directions
+    pub fn new<W: AsRef<[u8]>>(words: &[W], directions: DirectionSet) -> Self {
+        let mut patterns = Vec::with_capacity(words.len() * 2);
+        let mut texts = Vec::with_capacity(words.len() * 2);
+        for (index, word) in words.iter().enumerate() {
+            let word = word.as_ref();
+            patterns.push((index, false));
+            texts.push(word.to_vec());
+            patterns.push((index, true));
+            texts.push(word.iter().rev().copied().collect());
+        }
+
+        Scanner { automaton: AhoCorasick::new(&texts), patterns, directions }
+    }
+
+    /// Finds every occurrence of every word in the grid.
+    ///
+    /// Like `find_words`, overlapping occurrences are all reported and a word
+    /// reading the same both ways is found once per direction.
+    pub fn scan(&self, grid: &Grid<u8>) -> Vec<WordMatch> {
+        let mut matches = Vec::new();
+
+        for line in grid_lines(grid, self.directions) {
+            let offset = line.direction.offset();
+            self.automaton.for_each_match(&line.bytes, |pattern, end| {
+                let (word, reversed) = self.patterns[pattern];
+                let first = end - self.automaton.pattern_len(pattern);
+
+                // A reversed word is read backwards, from the end of the occurrence
+                let (index, direction) = if reversed {
+                    (end - 1, line.direction.opposite())
+                } else {
+                    (first, line.direction)
+                };
+                matches.push(WordMatch { word, start: line.start + offset * index as isize, direction });
+            });
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_search::find_words;
+
+    const GRID: &str = "\
+XMASAMX
+MMSAXSA
+AMAMMMS
+SXSAXMX
+";
+
+    /// The matches as `(word, start, step)`, in a fixed order
+    fn sorted(matches: Vec<WordMatch>) -> Vec<(usize, Position, Position)> {
+        let mut matches: Vec<_> = matches.iter().map(|m| (m.word, m.start, m.direction.offset())).collect();
+        matches.sort_unstable();
+        matches
+    }
+
+    #[test]
+    fn scanner_agrees_with_the_direct_search() {
+        let grid = Grid::parse(GRID, |c| c as u8).unwrap();
+        let words = ["XMAS", "AMA", "S", "ASA"];
+        for directions in [DirectionSet::Orthogonal, DirectionSet::Diagonal, DirectionSet::All] {
+            let scanned = Scanner::new(&words, directions).scan(&grid);
+            assert_eq!(sorted(scanned), sorted(find_words(&grid, &words, directions)), "{:?}", directions);
+        }
+    }
+
+    #[test]
+    fn palindromes_are_found_once_per_direction() {
+        let grid = Grid::parse("ABA\n", |c| c as u8).unwrap();
+        let matches = Scanner::new(&["ABA"], DirectionSet::Orthogonal).scan(&grid);
+        assert_eq!(matches.len(), 2);
+    }
+
+    #[test]
+    fn lines_cover_every_cell_once_per_direction() {
+        let grid = Grid::parse(GRID, |c| c as u8).unwrap();
+        let cells = grid.width() * grid.height();
+        for (directions, count) in [(DirectionSet::Orthogonal, 2), (DirectionSet::Diagonal, 2), (DirectionSet::All, 4)] {
+            let covered: usize = grid_lines(&grid, directions).iter().map(|line| line.bytes.len()).sum();
+            assert_eq!(covered, cells * count);
+        }
+    }
+}
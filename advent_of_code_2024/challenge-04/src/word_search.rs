@@ -0,0 +1,202 @@
+use aoc_common::{Direction, Grid, Position};
+
+/// The directions words can be read in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectionSet {
+    /// Left to right, right to left, top to bottom and bottom to top
+    Orthogonal,
+    /// The four diagonal directions
+    Diagonal,
+    /// Orthogonal and diagonal directions
+    All,
+}
+
+impl DirectionSet {
+    /// The directions in the set
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            DirectionSet::Orthogonal => &Direction::ORTHOGONAL,
+            DirectionSet::Diagonal => &Direction::DIAGONAL,
+            DirectionSet::All => &Direction::ALL,
+        }
+    }
+}
+
+/// A word found in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    pub word: usize,          // Index of the word in the list that was searched
+    pub start: Position,      // Position of the word's first letter
+    pub direction: Direction, // Direction the word is read in
+}
+
+/// Finds every occurrence of every word in `words`, read in any of the given
+/// directions. Overlapping occurrences are all reported, and a word reading
+/// the same both ways (like `ABA`) is found once per direction.
+pub fn find_words<W: AsRef<[u8]>>(grid: &Grid<u8>, words: &[W], directions: DirectionSet) -> Vec<WordMatch> {
+    let mut matches = Vec::new();
+
+    for (start, &cell) in grid.iter() {
+        for (index, word) in words.iter().enumerate() {
+            let word = word.as_ref();
+            // Skip empty words, and words that do not start with this letter
+            if word.first() != Some(&cell) {
+                continue;
+            }
+            for &direction in directions.directions() {
+                if reads_at(grid, word, start, direction) {
+                    matches.push(WordMatch { word: index, start, direction });
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+/// Checks if `word` can be read from `start` in `direction`
+fn reads_at(grid: &Grid<u8>, word: &[u8], start: Position, direction: Direction) -> bool {
+    word.iter()
+        .enumerate()
+        .all(|(i, letter)| grid.get(start + direction.offset() * i as isize) == Some(letter))
+}
+
+/// A pattern of letters at fixed offsets from an origin cell, e.g. the
+/// X-shaped cross of two `MAS` words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    cells: Vec<(Position, u8)>, // Offset from the origin and expected letter, sorted by offset
+}
+
+impl Template {
+    /// Builds a template from the letters expected at the given offsets
+    pub fn new(cells: impl IntoIterator<Item = (Position, u8)>) -> Self {
+        let mut cells: Vec<_> = cells.into_iter().collect();
+        cells.sort();
+        cells.dedup();
+        Template { cells }
+    }
+
+    /// Parses a template drawn as text, one line per row. The top-left corner
+    /// is the origin and `.` matches any letter, e.g. the X-MAS cross:
+    ///
+    /// ```text
+    /// M.S
+    /// .A.
+    /// M.S
+    /// ```
+    pub fn parse(text: &str) -> Self {
+        Template::new(text.lines().enumerate().flat_map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, letter)| letter != b'.')
+                .map(move |(x, letter)| (Position::new(x as isize, y as isize), letter))
+        }))
+    }
+
+    /// The offsets and letters of the template
+    pub fn cells(&self) -> &[(Position, u8)] {
+        &self.cells
+    }
+
+    /// Turns the template a quarter turn clockwise, moving it back so that
+    /// no offset is negative
+    pub fn rotate(&self) -> Self {
+        let rotated: Vec<_> = self
+            .cells
+            .iter()
+            .map(|&(offset, letter)| (Position::new(-offset.y, offset.x), letter))
+            .collect();
+        let min_x = rotated.iter().map(|(offset, _)| offset.x).min().unwrap_or(0);
+        let min_y = rotated.iter().map(|(offset, _)| offset.y).min().unwrap_or(0);
+        let shift = Position::new(min_x, min_y);
+        Template::new(rotated.into_iter().map(|(offset, letter)| (offset - shift, letter)))
+    }
+
+    /// The template in its four orientations, without duplicates for
+    /// symmetric templates
+    pub fn rotations(&self) -> Vec<Template> {
+        let mut rotations: Vec<Template> = Vec::with_capacity(4);
+        let mut template = self.clone();
+        for _ in 0..4 {
+            let next = template.rotate();
+            if !rotations.contains(&template) {
+                rotations.push(template);
+            }
+            template = next;
+        }
+        rotations
+    }
+
+    /// Checks if the template matches when its origin is placed at `origin`
+    pub fn matches_at(&self, grid: &Grid<u8>, origin: Position) -> bool {
+        self.cells
+            .iter()
+            .all(|(offset, letter)| grid.get(origin + *offset) == Some(letter))
+    }
+}
+
+/// A template found in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateMatch {
+    pub template: usize,  // Index of the template in the list that was searched
+    pub origin: Position, // Where the template's origin was placed
+}
+
+/// Finds every placement of every template in `templates`
+pub fn find_templates(grid: &Grid<u8>, templates: &[Template]) -> Vec<TemplateMatch> {
+    grid.positions()
+        .flat_map(|origin| {
+            templates
+                .iter()
+                .enumerate()
+                .filter(move |(_, template)| template.matches_at(grid, origin))
+                .map(move |(template, _)| TemplateMatch { template, origin })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X_MAS: &str = "M.S\n.A.\nM.S";
+
+    #[test]
+    fn parsing_skips_wildcards() {
+        let template = Template::parse(X_MAS);
+        assert_eq!(template.cells().len(), 5);
+        assert!(template.cells().contains(&(Position::new(1, 1), b'A')));
+        assert!(template.cells().contains(&(Position::new(2, 2), b'S')));
+    }
+
+    #[test]
+    fn rotations_stay_at_non_negative_offsets() {
+        let template = Template::parse("XMAS");
+        let rotations = template.rotations();
+        assert_eq!(rotations.len(), 4);
+        assert_eq!(rotations[1], Template::parse("X\nM\nA\nS"));
+        assert_eq!(rotations[2], Template::parse("SAMX"));
+        assert!(rotations.iter().flat_map(Template::cells).all(|(offset, _)| offset.x >= 0 && offset.y >= 0));
+        assert_eq!(rotations[3].rotate(), template);
+    }
+
+    #[test]
+    fn symmetric_templates_have_fewer_rotations() {
+        assert_eq!(Template::parse("A.A\n.A.\nA.A").rotations().len(), 1);
+        assert_eq!(Template::parse("AB\nBA").rotations().len(), 2);
+        assert_eq!(Template::parse(X_MAS).rotations().len(), 4);
+    }
+
+    #[test]
+    fn templates_match_inside_the_grid_only() {
+        let grid = Grid::parse("MMS\nMAS\nMSS\n", |c| c as u8).unwrap();
+        let templates = Template::parse(X_MAS).rotations();
+        let matches = find_templates(&grid, &templates);
+        assert_eq!(matches, [TemplateMatch { template: 0, origin: Position::new(0, 0) }]);
+
+        // Shifted so that part of the template falls off the grid
+        assert!(!templates[0].matches_at(&grid, Position::new(1, 0)));
+        assert!(!templates[0].matches_at(&grid, Position::new(-1, 0)));
+    }
+}
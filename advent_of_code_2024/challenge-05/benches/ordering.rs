@@ -0,0 +1,170 @@
+//! Compares `OrderingRules` with the topological sort it replaced, which
+//! rebuilt a graph for every update, on the example and on a synthetic input
+//! shaped like the real one.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hint::black_box;
+
+use challenge_05::OrderingRules;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+/// The previous implementation: the rules as a map from each page to the pages that must come before it
+mod baseline {
+    use super::*;
+
+    pub type GoesAfter = HashMap<i32, HashSet<i32>>;
+
+    pub fn fix_sequence(sequence: &[i32], goes_after: &GoesAfter) -> Vec<i32> {
+        let mut indegree: HashMap<i32, usize> = HashMap::new();
+        let mut graph: HashMap<i32, Vec<i32>> = HashMap::new();
+
+        for &page in sequence.iter() {
+            indegree.entry(page).or_insert(0);
+            if let Some(dependencies) = goes_after.get(&page) {
+                for &dep in dependencies.iter() {
+                    if sequence.contains(&dep) {
+                        graph.entry(dep).or_default().push(page);
+                        *indegree.entry(page).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        let mut queue: VecDeque<i32> = indegree.iter().filter(|&(_, &deg)| deg == 0).map(|(&page, _)| page).collect();
+        let mut sorted: Vec<i32> = Vec::new();
+
+        while let Some(page) = queue.pop_front() {
+            sorted.push(page);
+            if let Some(neighbors) = graph.get(&page) {
+                for &neighbor in neighbors {
+                    if let Some(deg) = indegree.get_mut(&neighbor) {
+                        *deg -= 1;
+                        if *deg == 0 {
+                            queue.push_back(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+
+        sorted
+    }
+
+    pub fn validate_sequence(sequence: &[i32], goes_after: &GoesAfter) -> bool {
+        let mut seen: HashSet<i32> = HashSet::new();
+        let sequence_set: HashSet<_> = sequence.iter().cloned().collect();
+
+        for val in sequence.iter() {
+            seen.insert(*val);
+            if let Some(v) = goes_after.get(val) {
+                for &dep in v.iter() {
+                    if !seen.contains(&dep) && sequence_set.contains(&dep) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Rules and updates of a benchmark input
+struct Workload {
+    name: &'static str,
+    rules: Vec<(i32, i32)>,
+    updates: Vec<Vec<i32>>,
+}
+
+impl Workload {
+    fn parse(name: &'static str, input: &str) -> Self {
+        let (rules, updates) = input.split_once("\n\n").expect("rules and updates are separated by a blank line");
+        let rules = rules
+            .lines()
+            .map(|line| {
+                let (before, after) = line.split_once('|').expect("a rule is `before|after`");
+                (before.trim().parse().unwrap(), after.trim().parse().unwrap())
+            })
+            .collect();
+        let updates = updates
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split(',').map(|page| page.trim().parse().unwrap()).collect())
+            .collect();
+        Workload { name, rules, updates }
+    }
+
+    /// Like the real input: 49 pages with a rule between every pair, and
+    /// updates of 5 to 23 distinct pages in shuffled order
+    fn synthetic() -> Self {
+        let pages: Vec<i32> = (11..=99).step_by(2).chain([10, 12, 14, 16]).collect();
+        let mut state: u64 = 0x5eed;
+        let mut next = move |bound: usize| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) as usize % bound
+        };
+
+        let mut rules = Vec::new();
+        for (i, &a) in pages.iter().enumerate() {
+            for &b in &pages[i + 1..] {
+                rules.push((a, b));
+            }
+        }
+
+        let updates = (0..200)
+            .map(|_| {
+                let mut pool = pages.clone();
+                let len = 5 + 2 * next(10);
+                (0..len).map(|_| pool.swap_remove(next(pool.len()))).collect()
+            })
+            .collect();
+
+        Workload { name: "synthetic", rules, updates }
+    }
+
+    fn goes_after(&self) -> baseline::GoesAfter {
+        let mut goes_after = baseline::GoesAfter::new();
+        for &(before, after) in &self.rules {
+            goes_after.entry(after).or_default().insert(before);
+        }
+        goes_after
+    }
+}
+
+fn ordering(c: &mut Criterion) {
+    for workload in [Workload::parse("example", EXAMPLE), Workload::synthetic()] {
+        let goes_after = workload.goes_after();
+        let rules: OrderingRules = workload.rules.iter().copied().collect();
+
+        let mut group = c.benchmark_group("validate");
+        group.bench_with_input(BenchmarkId::new("baseline", workload.name), &workload.updates, |b, updates| {
+            b.iter(|| updates.iter().filter(|update| baseline::validate_sequence(update, &goes_after)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("ordering_rules", workload.name), &workload.updates, |b, updates| {
+            b.iter(|| updates.iter().filter(|update| rules.validate(update)).count())
+        });
+        group.finish();
+
+        let mut group = c.benchmark_group("sort");
+        group.bench_with_input(BenchmarkId::new("baseline", workload.name), &workload.updates, |b, updates| {
+            b.iter(|| {
+                for update in updates {
+                    black_box(baseline::fix_sequence(update, &goes_after));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("ordering_rules", workload.name), &workload.updates, |b, updates| {
+            b.iter(|| {
+                for update in updates {
+                    black_box(rules.sort(update).unwrap());
+                }
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, ordering);
+criterion_main!(benches);
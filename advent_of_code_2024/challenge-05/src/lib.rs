@@ -0,0 +1,133 @@
+pub mod parse;
+pub mod rules;
+
+use std::collections::{BTreeSet, HashSet};
+use std::io::{self, Write};
+use aoc_common::solution::{Answer, Error, Solution};
+
+pub use parse::ParseError;
+pub use rules::{CycleError, OrderingRules, Violation};
+
+/// The page ordering rules and the updates to check against them
+pub struct PrintQueue {
+    // Rules telling which pages must appear before others
+    rules: OrderingRules,
+    // The same rules as `(before, after)`, in input order
+    rule_lines: Vec<(i32, i32)>,
+    // Vector to store sequences of numbers
+    arrays: Vec<Vec<i32>>,
+}
+
+impl PrintQueue {
+    /// The updates, in input order
+    pub fn updates(&self) -> &[Vec<i32>] {
+        &self.arrays
+    }
+
+    /// The page ordering rules
+    pub fn ordering_rules(&self) -> &OrderingRules {
+        &self.rules
+    }
+
+    /// Whether the update already follows the ordering rules
+    pub fn is_ordered(&self, update: &[i32]) -> bool {
+        self.rules.validate(update)
+    }
+
+    /// Puts the pages of the update in the order given by the rules,
+    /// or returns a cycle of rules between its pages if there is no such order
+    pub fn fix(&self, update: &[i32]) -> Result<Vec<i32>, CycleError> {
+        self.rules.sort(update)
+    }
+
+    /// Every rule as `(before, after)`, sorted
+    pub fn rules(&self) -> BTreeSet<(i32, i32)> {
+        self.rule_lines.iter().copied().collect()
+    }
+
+    /// Pages that appear in at least one update
+    fn seen_pages(&self) -> HashSet<i32> {
+        self.arrays.iter().flatten().copied().collect()
+    }
+
+    /// Rules, as `(before, after)`, that mention a page never printed by any update.
+    ///
+    /// Such rules can never apply, which usually points at a typo in the input.
+    pub fn rules_with_unseen_pages(&self) -> Vec<(i32, i32)> {
+        let seen = self.seen_pages();
+        self.rules()
+            .into_iter()
+            .filter(|(before, after)| !seen.contains(before) || !seen.contains(after))
+            .collect()
+    }
+
+    /// Writes the rule graph in Graphviz DOT format, with an edge from every page
+    /// to each page that must come after it.
+    ///
+    /// Pages that no update prints are drawn dashed.
+    pub fn write_dot<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let seen = self.seen_pages();
+        let rules = self.rules();
+        let pages: BTreeSet<i32> = rules.iter().flat_map(|&(before, after)| [before, after]).collect();
+
+        writeln!(writer, "digraph rules {{")?;
+        for page in pages.iter().filter(|page| !seen.contains(page)) {
+            writeln!(writer, "    {} [style=dashed];", page)?;
+        }
+        for (before, after) in rules {
+            writeln!(writer, "    {} -> {};", before, after)?;
+        }
+        writeln!(writer, "}}")?;
+
+        writer.flush()
+    }
+}
+
+/// Solver for Day 5: Print Queue
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    type Input = PrintQueue;
+
+    fn parse(input: &str) -> Result<PrintQueue, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(queue: &PrintQueue) -> Result<Answer, Error> {
+        // Variable to store the total of the middle values of valid sequences
+        let mut total: i32 = 0;
+
+        for sequence in queue.arrays.iter() {
+            if queue.rules.validate(sequence) {
+                // If the sequence is valid, calculate and add the middle value to the total
+                total += middle_page(sequence);
+            }
+        }
+
+        Ok(total.into())
+    }
+
+    fn part2(queue: &PrintQueue) -> Result<Answer, Error> {
+        // Variable to store the total of the middle values of corrected sequences
+        let mut incorrect_total: i32 = 0;
+
+        for (index, sequence) in queue.arrays.iter().enumerate() {
+            if !queue.rules.validate(sequence) {
+                // If the sequence is invalid, fix it and add the middle value of the corrected sequence
+                let fixed_sequence = queue.rules.sort(sequence)
+                    .map_err(|e| format!("update {}: {}", index + 1, e))?;
+                incorrect_total += middle_page(&fixed_sequence);
+            }
+        }
+
+        Ok(incorrect_total.into())
+    }
+}
+
+/// The middle page of an update; parsing guarantees every update has one
+fn middle_page(update: &[i32]) -> i32 {
+    update[update.len() / 2]
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::parse::parse_separated;
+
+use crate::{OrderingRules, PrintQueue};
+
+/// Error returned when the rules and updates cannot be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input has no blank line between the rules and the updates
+    MissingSeparator,
+    /// A line of the rules section that is not `before|after`
+    MalformedRule { line: usize, text: String },
+    /// A line of the updates section that is not a comma-separated list of pages
+    MalformedUpdate { line: usize, text: String },
+    /// An update that prints the same page more than once
+    RepeatedPage { line: usize, page: i32 },
+    /// An update with an even number of pages, which has no middle page
+    EvenUpdate { line: usize, len: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSeparator => {
+                write!(f, "expected a blank line between the rules and the updates")
+            }
+            ParseError::MalformedRule { line, text } => {
+                write!(f, "line {}: expected a rule like `47|53`, found {:?}", line, text)
+            }
+            ParseError::MalformedUpdate { line, text } => {
+                write!(f, "line {}: expected an update like `75,47,61`, found {:?}", line, text)
+            }
+            ParseError::RepeatedPage { line, page } => {
+                write!(f, "line {}: page {} is printed more than once", line, page)
+            }
+            ParseError::EvenUpdate { line, len } => {
+                write!(f, "line {}: an update of {} pages has no middle page", line, len)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a rule like `47|53` into `(47, 53)`, allowing spaces around the pages
+fn parse_rule(line: usize, text: &str) -> Result<(i32, i32), ParseError> {
+    match parse_separated(text, '|').as_deref() {
+        Ok(&[before, after]) => Ok((before, after)),
+        _ => Err(ParseError::MalformedRule { line, text: text.to_string() }),
+    }
+}
+
+/// Parses an update like `75,47,61`, which must print each page once and have a single middle page
+fn parse_update(line: usize, text: &str) -> Result<Vec<i32>, ParseError> {
+    let update: Vec<i32> =
+        parse_separated(text, ',').map_err(|_| ParseError::MalformedUpdate { line, text: text.to_string() })?;
+
+    let mut seen = HashSet::new();
+    if let Some(&page) = update.iter().find(|&&page| !seen.insert(page)) {
+        return Err(ParseError::RepeatedPage { line, page });
+    }
+    if update.len().is_multiple_of(2) {
+        return Err(ParseError::EvenUpdate { line, len: update.len() });
+    }
+
+    Ok(update)
+}
+
+impl FromStr for PrintQueue {
+    type Err = ParseError;
+
+    /// Parses the rules, one per line, then a blank line, then the updates, one per line.
+    ///
+    /// Blank lines after the last update are ignored.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines().enumerate().map(|(index, text)| (index + 1, text));
+
+        // Rules come first, up to the blank line
+        let mut rule_lines = Vec::new();
+        loop {
+            match lines.next() {
+                Some((_, text)) if text.trim().is_empty() => break,
+                Some((line, text)) => rule_lines.push(parse_rule(line, text)?),
+                None => return Err(ParseError::MissingSeparator),
+            }
+        }
+
+        // Then every remaining line is an update, up to the trailing blank lines
+        let mut update_lines: Vec<(usize, &str)> = lines.collect();
+        while update_lines.last().is_some_and(|(_, text)| text.trim().is_empty()) {
+            update_lines.pop();
+        }
+        let arrays = update_lines
+            .into_iter()
+            .map(|(line, text)| parse_update(line, text))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rules: OrderingRules = rule_lines.iter().copied().collect();
+        Ok(PrintQueue { rules, rule_lines, arrays })
+    }
+}
+
+/// Writes the rules and updates back in the input format
+impl fmt::Display for PrintQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (before, after) in &self.rule_lines {
+            writeln!(f, "{}|{}", before, after)?;
+        }
+        writeln!(f)?;
+
+        for update in &self.arrays {
+            let pages: Vec<String> = update.iter().map(|page| page.to_string()).collect();
+            writeln!(f, "{}", pages.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<PrintQueue, ParseError> {
+        input.parse()
+    }
+
+    #[test]
+    fn the_updates_may_be_missing_or_followed_by_blank_lines() {
+        assert!(parse("1|2\n\n").unwrap().updates().is_empty());
+        assert!(parse("1|2\n\n \n").unwrap().updates().is_empty());
+        assert_eq!(parse("1|2\n\n1,2,3\n\n\n").unwrap().updates(), [vec![1, 2, 3]]);
+        assert_eq!(parse("1|2\r\n\r\n2,1,3\r\n").unwrap().updates(), [vec![2, 1, 3]]);
+        assert!(parse("\n3\n").is_ok_and(|queue| queue.rules().is_empty()));
+    }
+
+    #[test]
+    fn a_missing_blank_line_is_reported() {
+        assert_eq!(parse("").err(), Some(ParseError::MissingSeparator));
+        assert_eq!(parse("1|2\n2|3").err(), Some(ParseError::MissingSeparator));
+    }
+
+    #[test]
+    fn errors_point_at_the_line_of_the_input() {
+        assert_eq!(
+            parse("1|2\n2-3\n\n1,2,3\n").err(),
+            Some(ParseError::MalformedRule { line: 2, text: "2-3".to_string() })
+        );
+        assert_eq!(
+            parse("1|2\n\n1,2,3\n\n4,5,6\n").err(),
+            Some(ParseError::MalformedUpdate { line: 4, text: String::new() })
+        );
+        assert_eq!(parse("1|2\n\n1,2,3\n1,2\n").err(), Some(ParseError::EvenUpdate { line: 4, len: 2 }));
+        assert_eq!(parse("1|5\n\n5,1,1,1,1\n").err(), Some(ParseError::RepeatedPage { line: 3, page: 1 }));
+        assert_eq!(
+            parse("1|2\n\n2,1,2\n").err().map(|e| e.to_string()).as_deref(),
+            Some("line 3: page 2 is printed more than once")
+        );
+        assert_eq!(
+            parse("1|2\n\n1,2,3\n1,x,3\n").err().map(|e| e.to_string()).as_deref(),
+            Some("line 4: expected an update like `75,47,61`, found \"1,x,3\"")
+        );
+    }
+
+    #[test]
+    fn display_writes_the_input_back() {
+        let input = "47|53\n97|13\n\n75,47,61,53,29\n61,13,29\n";
+        assert_eq!(parse(input).unwrap().to_string(), input);
+        assert_eq!(parse("1 | 2\n\n 3 , 4 , 5\n").unwrap().to_string(), "1|2\n\n3,4,5\n");
+    }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+/// Pages below this number are looked up in the dense precedence matrix
+const DENSE_PAGES: usize = 100;
+
+/// Error returned when the rules that apply to an update contradict each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// Pages of the cycle, each of which must come before the next; the last one must come before the first
+    pub cycle: Vec<i32>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pages: Vec<String> = self.cycle.iter().chain(self.cycle.first()).map(|page| page.to_string()).collect();
+        write!(f, "the ordering rules form a cycle: {}", pages.join(" -> "))
+    }
+}
+
+impl Error for CycleError {}
+
+/// Two pages of an update printed in the wrong order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub earlier: usize, // Index of the page printed first
+    pub later: usize,   // Index of a page that must be printed before it
+}
+
+/// The page ordering rules, compiled once for constant-time lookups.
+///
+/// Rules between pages 0 to 99 live in a dense precedence matrix; rules
+/// mentioning any other page fall back to a hash set.
+#[derive(Clone)]
+pub struct OrderingRules {
+    dense: Vec<bool>,             // `dense[a * DENSE_PAGES + b]` is set if a must come before b
+    sparse: HashSet<(i32, i32)>, // Rules `(before, after)` with a page outside the matrix
+}
+
+impl OrderingRules {
+    /// Creates an empty set of rules
+    pub fn new() -> Self {
+        OrderingRules { dense: vec![false; DENSE_PAGES * DENSE_PAGES], sparse: HashSet::new() }
+    }
+
+    /// Index of the rule in the dense matrix, if both pages fit in it
+    fn dense_index(before: i32, after: i32) -> Option<usize> {
+        let before = usize::try_from(before).ok().filter(|&page| page < DENSE_PAGES)?;
+        let after = usize::try_from(after).ok().filter(|&page| page < DENSE_PAGES)?;
+        Some(before * DENSE_PAGES + after)
+    }
+
+    /// Adds the rule `before|after`
+    pub fn insert(&mut self, before: i32, after: i32) {
+        match Self::dense_index(before, after) {
+            Some(index) => self.dense[index] = true,
+            None => {
+                self.sparse.insert((before, after));
+            }
+        }
+    }
+
+    /// Whether a rule says `before` must be printed before `after`
+    pub fn must_precede(&self, before: i32, after: i32) -> bool {
+        match Self::dense_index(before, after) {
+            Some(index) => self.dense[index],
+            None => self.sparse.contains(&(before, after)),
+        }
+    }
+
+    /// Every rule as `(before, after)`, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let dense = self.dense.iter().enumerate().filter(|&(_, &rule)| rule).map(|(index, _)| {
+            ((index / DENSE_PAGES) as i32, (index % DENSE_PAGES) as i32)
+        });
+        dense.chain(self.sparse.iter().copied())
+    }
+
+    /// Orders two pages by the rules; pages without a rule between them compare equal
+    pub fn compare(&self, a: i32, b: i32) -> Ordering {
+        if self.must_precede(a, b) {
+            Ordering::Less
+        } else if self.must_precede(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// The first pair of pages printed in the wrong order, scanning from the start of the update
+    pub fn first_violation(&self, pages: &[i32]) -> Option<Violation> {
+        (0..pages.len()).find_map(|earlier| {
+            (earlier + 1..pages.len())
+                .find(|&later| self.must_precede(pages[later], pages[earlier]))
+                .map(|later| Violation { earlier, later })
+        })
+    }
+
+    /// Whether the update follows every rule between its pages
+    pub fn validate(&self, pages: &[i32]) -> bool {
+        self.first_violation(pages).is_none()
+    }
+
+    /// Puts the pages of the update in the order given by the rules.
+    ///
+    /// When the rules order every pair of pages consistently, this is a plain
+    /// `sort_by` with the rule comparator; otherwise it falls back to a
+    /// topological sort, which fails with a cycle of rules if there is no valid order.
+    pub fn sort(&self, pages: &[i32]) -> Result<Vec<i32>, CycleError> {
+        if self.is_total_order(pages) {
+            let mut sorted = pages.to_vec();
+            sorted.sort_by(|&a, &b| self.compare(a, b));
+            Ok(sorted)
+        } else {
+            self.topological_sort(pages)
+        }
+    }
+
+    /// Whether the rules order the distinct pages of the update as a strict total order.
+    ///
+    /// With exactly one rule between every pair of pages, the order is
+    /// transitive if and only if every page has a different number of pages
+    /// after it.
+    fn is_total_order(&self, pages: &[i32]) -> bool {
+        let mut seen = HashSet::new();
+        if !pages.iter().all(|&page| seen.insert(page)) {
+            return false; // Repeated pages compare equal to themselves
+        }
+
+        let mut followers = vec![false; pages.len()];
+        for &a in pages {
+            if self.must_precede(a, a) {
+                return false;
+            }
+
+            let mut count = 0;
+            for &b in pages.iter().filter(|&&b| b != a) {
+                match (self.must_precede(a, b), self.must_precede(b, a)) {
+                    (true, false) => count += 1,
+                    (false, true) => {}
+                    _ => return false, // No rule, or rules both ways
+                }
+            }
+            if std::mem::replace(&mut followers[count], true) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Kahn's topological sort of the pages of the update; repeated pages are placed
+    /// once, then written out as many times as the update prints them
+    fn topological_sort(&self, update: &[i32]) -> Result<Vec<i32>, CycleError> {
+        let mut pages: Vec<i32> = Vec::new();
+        let mut copies: Vec<usize> = Vec::new();
+        for &page in update {
+            match pages.iter().position(|&distinct| distinct == page) {
+                Some(i) => copies[i] += 1,
+                None => {
+                    pages.push(page);
+                    copies.push(1);
+                }
+            }
+        }
+
+        // Number of unsorted pages that must come before each page
+        let mut indegree: Vec<usize> = pages
+            .iter()
+            .map(|&page| pages.iter().filter(|&&other| self.must_precede(other, page)).count())
+            .collect();
+
+        let mut ready: Vec<usize> = (0..pages.len()).filter(|&i| indegree[i] == 0).collect();
+        let mut sorted = Vec::with_capacity(update.len());
+
+        while let Some(i) = ready.pop() {
+            sorted.extend(std::iter::repeat_n(pages[i], copies[i]));
+            for j in 0..pages.len() {
+                if self.must_precede(pages[i], pages[j]) {
+                    indegree[j] -= 1;
+                    if indegree[j] == 0 {
+                        ready.push(j);
+                    }
+                }
+            }
+        }
+
+        if sorted.len() < update.len() {
+            let remaining: Vec<i32> = (0..pages.len()).filter(|&i| indegree[i] > 0).map(|i| pages[i]).collect();
+            return Err(CycleError { cycle: self.find_cycle(&remaining) });
+        }
+
+        Ok(sorted)
+    }
+
+    /// Finds a cycle among the pages that topological sorting could not place.
+    ///
+    /// Every such page still has a page before it among them, so walking from
+    /// page to page must eventually come back to a page already visited.
+    fn find_cycle(&self, remaining: &[i32]) -> Vec<i32> {
+        let mut page = remaining.iter().copied().min().expect("a cycle leaves pages unsorted");
+        let mut path: Vec<i32> = Vec::new();
+
+        while !path.contains(&page) {
+            path.push(page);
+            page = remaining
+                .iter()
+                .copied()
+                .filter(|&before| self.must_precede(before, page))
+                .min()
+                .expect("an unsorted page has an unsorted page before it");
+        }
+
+        // The path was walked backwards, from each page to one that must come before it
+        let start = path.iter().position(|&visited| visited == page).unwrap_or(0);
+        let mut cycle = path.split_off(start);
+        cycle.reverse();
+
+        // Start from the smallest page so the same cycle is always reported the same way
+        let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+        cycle.rotate_left(smallest);
+        cycle
+    }
+}
+
+impl Default for OrderingRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for OrderingRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderingRules").field("rules", &self.iter().count()).finish()
+    }
+}
+
+impl Extend<(i32, i32)> for OrderingRules {
+    fn extend<I: IntoIterator<Item = (i32, i32)>>(&mut self, rules: I) {
+        for (before, after) in rules {
+            self.insert(before, after);
+        }
+    }
+}
+
+impl FromIterator<(i32, i32)> for OrderingRules {
+    fn from_iter<I: IntoIterator<Item = (i32, i32)>>(rules: I) -> Self {
+        let mut ordering = OrderingRules::new();
+        ordering.extend(rules);
+        ordering
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_outside_the_matrix_are_kept_too() {
+        let rules: OrderingRules = [(1, 2), (99, 100), (-1, 5), (250, 3)].into_iter().collect();
+        assert!(rules.must_precede(1, 2) && !rules.must_precede(2, 1));
+        assert!(rules.must_precede(99, 100) && rules.must_precede(-1, 5) && rules.must_precede(250, 3));
+        assert!(!rules.must_precede(100, 99));
+
+        let mut all: Vec<(i32, i32)> = rules.iter().collect();
+        all.sort_unstable();
+        assert_eq!(all, [(-1, 5), (1, 2), (99, 100), (250, 3)]);
+    }
+
+    #[test]
+    fn total_orders_and_partial_orders_sort_alike() {
+        let total: OrderingRules = [(1, 2), (1, 3), (2, 3)].into_iter().collect();
+        assert!(total.is_total_order(&[3, 1, 2]));
+        assert_eq!(total.sort(&[3, 1, 2]), Ok(vec![1, 2, 3]));
+
+        // No rule between 1 and 3, so the topological sort has to place them
+        let partial: OrderingRules = [(1, 2), (2, 3)].into_iter().collect();
+        assert!(!partial.is_total_order(&[3, 1, 2]));
+        assert_eq!(partial.sort(&[3, 1, 2]), Ok(vec![1, 2, 3]));
+        assert_eq!(partial.first_violation(&[3, 1, 2]), Some(Violation { earlier: 0, later: 2 }));
+    }
+
+    #[test]
+    fn cycles_are_reported_from_their_smallest_page() {
+        let rules: OrderingRules = [(3, 1), (1, 2), (2, 3), (4, 1)].into_iter().collect();
+        assert!(!rules.is_total_order(&[2, 4, 3, 1]));
+
+        // Page 4 sorts fine, but is not part of the cycle
+        let error = rules.sort(&[2, 4, 3, 1]).unwrap_err();
+        assert_eq!(error.cycle, [1, 2, 3]);
+        assert_eq!(error.to_string(), "the ordering rules form a cycle: 1 -> 2 -> 3 -> 1");
+
+        // Rules that only apply to other pages are ignored
+        assert_eq!(rules.sort(&[4, 2, 1]), Ok(vec![4, 1, 2]));
+    }
+
+    #[test]
+    fn rules_both_ways_and_onto_itself_are_cycles() {
+        let rules: OrderingRules = [(10, 20), (20, 10), (7, 7), (150, 300), (300, 150)].into_iter().collect();
+        assert_eq!(rules.sort(&[20, 10, 30]).unwrap_err().cycle, [10, 20]);
+        assert_eq!(rules.sort(&[7, 8]).unwrap_err().cycle, [7]);
+        assert_eq!(rules.sort(&[300, 150]).unwrap_err().cycle, [150, 300]);
+    }
+
+    #[test]
+    fn repeated_pages_keep_every_copy() {
+        let rules: OrderingRules = [(1, 2)].into_iter().collect();
+        assert!(!rules.is_total_order(&[2, 1, 2]));
+        assert_eq!(rules.sort(&[2, 1, 2]), Ok(vec![1, 2, 2]));
+
+        let rules: OrderingRules = [(1, 5)].into_iter().collect();
+        assert_eq!(rules.sort(&[5, 1, 1, 1, 1]), Ok(vec![1, 1, 1, 1, 5]));
+    }
+}
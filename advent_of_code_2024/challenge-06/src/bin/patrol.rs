@@ -0,0 +1,105 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::input::{read_input, DEFAULT_INPUT};
+use aoc_common::solution::{exit_code, Error, Solution};
+use aoc_common::Position;
+use challenge_06::visualize::{Outcome, Recording};
+use challenge_06::{obstructions_that_cause_loop, Day06};
+use clap::Parser;
+
+/// Animates the guard's patrol in the terminal and exports it as images.
+#[derive(Parser)]
+#[command(name = "patrol")]
+struct Cli {
+    /// Puzzle input file
+    #[arg(default_value = DEFAULT_INPUT)]
+    input: PathBuf,
+
+    /// Place an extra obstruction at X,Y
+    #[arg(long, value_parser = parse_position, conflicts_with = "loop_number")]
+    obstruction: Option<Position>,
+
+    /// Place the N-th obstruction (from 1) that causes a loop, in patrol order
+    #[arg(long = "loop", value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    loop_number: Option<u64>,
+
+    /// Milliseconds between two frames
+    #[arg(long, default_value_t = 50)]
+    delay: u64,
+
+    /// Do not play the animation in the terminal
+    #[arg(long)]
+    quiet: bool,
+
+    /// Write every frame as a PPM image into this directory
+    #[arg(long, value_name = "DIR")]
+    ppm: Option<PathBuf>,
+
+    /// Write the whole patrol as an animated GIF
+    #[arg(long, value_name = "FILE")]
+    gif: Option<PathBuf>,
+
+    /// Side in pixels of one cell in exported images
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    scale: u16,
+}
+
+/// Parses a position written as `X,Y`
+fn parse_position(text: &str) -> Result<Position, String> {
+    let (x, y) = text.split_once(',').ok_or("expected X,Y")?;
+    let coordinate = |c: &str| c.trim().parse::<isize>().map_err(|e| e.to_string());
+    Ok(Position::new(coordinate(x)?, coordinate(y)?))
+}
+
+fn main() -> ExitCode {
+    exit_code(run(Cli::parse()))
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    let input = read_input(&cli.input).map_err(|e| format!("cannot read {}: {}", cli.input.display(), e))?;
+    let lab = Day06::parse(&input)?;
+
+    let obstruction = match cli.loop_number {
+        Some(n) => {
+            let obstructions = obstructions_that_cause_loop(lab.grid(), lab.guard());
+            let found = obstructions.len();
+            Some(*obstructions.get(n as usize - 1).ok_or_else(|| format!("only {} obstructions cause a loop", found))?)
+        }
+        None => cli.obstruction,
+    };
+
+    let recording = Recording::record(&lab, obstruction)?;
+    let delay = Duration::from_millis(cli.delay);
+    let scale = cli.scale as usize;
+
+    if !cli.quiet {
+        recording.play(io::stdout().lock(), delay)?;
+    }
+
+    if let Some(dir) = &cli.ppm {
+        fs::create_dir_all(dir)?;
+        for frame in recording.frames() {
+            let path = dir.join(format!("frame_{:05}.ppm", frame.step()));
+            frame.write_ppm(BufWriter::new(File::create(path)?), scale)?;
+        }
+        eprintln!("Wrote {} frames to {}", recording.len(), dir.display());
+    }
+
+    if let Some(path) = &cli.gif {
+        recording.write_gif(BufWriter::new(File::create(path)?), scale, delay)?;
+        eprintln!("Wrote {}", path.display());
+    }
+
+    if cli.quiet {
+        match recording.outcome() {
+            Outcome::LeftMap => println!("The guard left the map after {} steps", recording.len() - 1),
+            Outcome::Loop => println!("The guard is stuck in a loop after {} steps", recording.len() - 1),
+        }
+    }
+
+    Ok(())
+}
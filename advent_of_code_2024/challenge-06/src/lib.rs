@@ -0,0 +1,148 @@
+mod loop_search;
+mod progress;
+pub mod visualize;
+
+use std::collections::HashSet;
+use aoc_common::solution::{Answer, Error, Solution};
+use aoc_common::{Direction, Grid, Position};
+
+pub use loop_search::{count_obstructions_that_cause_loop, obstructions_that_cause_loop};
+pub use progress::Progress;
+
+/// Represents the guard's current state: position and direction
+struct Guard {
+    position: Position,   // Current coordinates of the guard
+    direction: Direction, // Current direction the guard is facing
+}
+
+impl Guard {
+    /// Constructs a new Guard starting at `position` facing a given direction.
+    fn new(position: Position, direction: Direction) -> Self {
+        Guard {
+            position,
+            direction,
+        }
+    }
+
+    /// Determines the next direction for the guard based on the environment
+    ///
+    /// The guard checks the position in front of it:
+    /// - If there is an obstacle or the position is out of bounds, the guard will turn right;
+    /// - Otherwise, the guard continues forward in the same direction.
+    fn next_direction(&self, grid: &Grid<char>) -> Direction {
+        // Look at the cell in front of the guard; out of bounds means the guard keeps going
+        match grid.get(self.position.step(self.direction)) {
+            // If an obstacle is encountered, turn right
+            Some('#') => self.direction.turn_right(),
+            // No obstacle: continue in the current direction
+            _ => self.direction,
+        }
+    }
+
+    /// Moves the guard one step forward based on the current direction.
+    fn move_forward(&mut self) {
+        self.position = self.position.step(self.direction);
+    }
+}
+
+/// Runs a simulation of the guard's patrol on the given grid.
+///
+/// Returns a tuple:
+/// (number_of_visited_positions, leaves_grid, in_loop)
+/// - number_of_visited_positions: Count of distinct positions visited (including the start)
+/// - leaves_grid: true if the guard eventually leaves the grid
+/// - in_loop: true if the guard gets stuck in a loop
+fn simulate_patrol(grid: &Grid<char>, start: Position) -> (usize, bool, bool) {
+    let mut guard = Guard::new(start, Direction::Up);
+
+    // Keep track of visited positions to count distinct visits
+    let mut visited_positions = HashSet::new();
+
+    // Each state: (position, direction), needed to detect loops
+    let mut states = HashSet::new();
+
+    // Record the initial state and position
+    states.insert((guard.position, guard.direction));
+    visited_positions.insert(guard.position);
+
+    loop {
+        let next_direction = guard.next_direction(grid);
+
+        // If the direction remains unchanged, move forward
+        if next_direction == guard.direction {
+            guard.move_forward();
+        }
+        guard.direction = next_direction;
+
+        // Check if guard leaves the grid after moving
+        if !grid.in_bounds(guard.position) {
+            // Guard left the mapped area
+            return (visited_positions.len(), true, false);
+        }
+
+        // Mark the new position as visited
+        visited_positions.insert(guard.position);
+
+        let state = (guard.position, guard.direction);
+        if states.contains(&state) {
+            // We have encountered this state before -> loop detected
+            return (visited_positions.len(), false, true);
+        } else {
+            states.insert(state);
+        }
+    }
+}
+
+/// The lab map together with the guard's starting position
+pub struct Lab {
+    grid: Grid<char>,  // The map, with '#' marking obstacles
+    guard: Position,   // The guard's initial position (marked as '^')
+}
+
+impl Lab {
+    /// The map, with '#' marking obstacles
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    /// The guard's initial position
+    pub fn guard(&self) -> Position {
+        self.guard
+    }
+
+    /// Number of distinct cells the guard visits before leaving the lab, or `None` if it walks in a loop
+    pub fn patrol_length(&self) -> Option<usize> {
+        let (visited_count, leaves_grid, _) = simulate_patrol(&self.grid, self.guard);
+        leaves_grid.then_some(visited_count)
+    }
+}
+
+/// Solver for Day 6: Guard Gallivant
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+
+    type Input = Lab;
+
+    fn parse(input: &str) -> Result<Lab, Error> {
+        let grid = Grid::parse(input, |c| c)?;
+
+        // Locate the guard's initial position in the grid (marked as '^')
+        let guard = grid.position_of(|&ch| ch == '^').ok_or("the map has no guard ('^')")?;
+
+        Ok(Lab { grid, guard })
+    }
+
+    fn part1(lab: &Lab) -> Result<Answer, Error> {
+        // Simulate the guard's initial patrol without modifications
+        let (visited_count, _, _) = simulate_patrol(&lab.grid, lab.guard);
+        Ok(visited_count.into())
+    }
+
+    fn part2(lab: &Lab) -> Result<Answer, Error> {
+        // Count how many positions can cause the guard to loop if obstructed
+        Ok(count_obstructions_that_cause_loop(&lab.grid, lab.guard, None).into())
+    }
+}
@@ -0,0 +1,387 @@
+use aoc_common::{Direction, Grid, Position};
+
+use crate::Guard;
+use crate::progress::Progress;
+
+/// Marker for "the guard walks off the map" in the jump table
+const EXIT: u32 = u32::MAX;
+
+/// Index of an orthogonal direction in the tables below
+pub(crate) fn direction_index(direction: Direction) -> usize {
+    match direction {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        _ => unreachable!("the guard only moves orthogonally"),
+    }
+}
+
+/// Precomputed "next obstacle in direction" for every cell of the map.
+///
+/// For every cell and direction it stores the cell where the guard stops,
+/// right in front of the next obstacle, or `EXIT` if it walks off the map.
+struct JumpTable {
+    width: usize,
+    stops: Vec<u32>, // Indexed by `cell * 4 + direction`
+}
+
+impl JumpTable {
+    /// Builds the table by sweeping every row and column once per direction.
+    fn new(grid: &Grid<char>) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let mut stops = vec![EXIT; width * height * 4];
+        let is_obstacle = |x: usize, y: usize| grid[Position::new(x as isize, y as isize)] == '#';
+
+        for x in 0..width {
+            // Walking up stops below the closest obstacle above
+            let mut stop = EXIT;
+            for y in 0..height {
+                if is_obstacle(x, y) {
+                    stop = ((y + 1) * width + x) as u32;
+                } else {
+                    stops[(y * width + x) * 4 + direction_index(Direction::Up)] = stop;
+                }
+            }
+
+            // Walking down stops above the closest obstacle below
+            let mut stop = EXIT;
+            for y in (0..height).rev() {
+                if is_obstacle(x, y) {
+                    stop = if y > 0 { ((y - 1) * width + x) as u32 } else { EXIT };
+                } else {
+                    stops[(y * width + x) * 4 + direction_index(Direction::Down)] = stop;
+                }
+            }
+        }
+
+        for y in 0..height {
+            // Walking left stops right of the closest obstacle on the left
+            let mut stop = EXIT;
+            for x in 0..width {
+                if is_obstacle(x, y) {
+                    stop = (y * width + x + 1) as u32;
+                } else {
+                    stops[(y * width + x) * 4 + direction_index(Direction::Left)] = stop;
+                }
+            }
+
+            // Walking right stops left of the closest obstacle on the right
+            let mut stop = EXIT;
+            for x in (0..width).rev() {
+                if is_obstacle(x, y) {
+                    stop = if x > 0 { (y * width + x - 1) as u32 } else { EXIT };
+                } else {
+                    stops[(y * width + x) * 4 + direction_index(Direction::Right)] = stop;
+                }
+            }
+        }
+
+        JumpTable { width, stops }
+    }
+
+    /// Where the guard stops when walking straight from `position`, if it stays on the map
+    fn stop(&self, position: Position, direction: Direction) -> Option<Position> {
+        let stop = self.stops[self.cell(position) * 4 + direction_index(direction)];
+        (stop != EXIT).then(|| {
+            let stop = stop as usize;
+            Position::new((stop % self.width) as isize, (stop / self.width) as isize)
+        })
+    }
+
+    /// Index of the cell at `position`
+    fn cell(&self, position: Position) -> usize {
+        position.y as usize * self.width + position.x as usize
+    }
+}
+
+/// A set of guard states (cell, direction), stored as a flat bitset.
+///
+/// Only the words that were written are cleared between two searches.
+struct StateSet {
+    words: Vec<u64>,
+    touched: Vec<usize>,
+}
+
+impl StateSet {
+    fn new(states: usize) -> Self {
+        StateSet { words: vec![0; states.div_ceil(64)], touched: Vec::new() }
+    }
+
+    /// Adds a state, returning false if it was already present
+    fn insert(&mut self, state: usize) -> bool {
+        let (word, bit) = (state / 64, 1u64 << (state % 64));
+        if self.words[word] & bit != 0 {
+            return false;
+        }
+        if self.words[word] == 0 {
+            self.touched.push(word);
+        }
+        self.words[word] |= bit;
+        true
+    }
+
+    fn clear(&mut self) {
+        for word in self.touched.drain(..) {
+            self.words[word] = 0;
+        }
+    }
+}
+
+/// A cell the guard walks into on its original patrol, together with the
+/// guard's state right before it first steps there.
+struct Candidate {
+    cell: Position,
+    guard: Guard,
+}
+
+/// Walks the original patrol and returns every cell it reaches, except the
+/// starting one, in the order they are first visited.
+///
+/// Only these cells can change the patrol when an obstruction is placed on them.
+fn patrol_candidates(grid: &Grid<char>, start: Position) -> Vec<Candidate> {
+    let mut guard = Guard::new(start, Direction::Up);
+    let mut visited = grid.map(|_| false);
+    let mut states = StateSet::new(grid.width() * grid.height() * 4);
+    let mut candidates = Vec::new();
+    visited[start] = true;
+
+    loop {
+        let next_direction = guard.next_direction(grid);
+
+        // If the direction remains unchanged, move forward
+        if next_direction == guard.direction {
+            let next = guard.position.step(guard.direction);
+            if !grid.in_bounds(next) {
+                return candidates;
+            }
+            if !visited[next] {
+                visited[next] = true;
+                candidates.push(Candidate { cell: next, guard: Guard::new(guard.position, guard.direction) });
+            }
+            guard.move_forward();
+        }
+        guard.direction = next_direction;
+
+        // Stop if the original patrol is already a loop
+        let cell = guard.position.y as usize * grid.width() + guard.position.x as usize;
+        if !states.insert(cell * 4 + direction_index(guard.direction)) {
+            return candidates;
+        }
+    }
+}
+
+/// Number of steps from `from` to `to` when walking in `direction`, if `to` lies ahead
+fn distance_ahead(from: Position, to: Position, direction: Direction) -> Option<isize> {
+    let delta = to - from;
+    let offset = direction.offset();
+    let steps = if offset.x == 0 { delta.y * offset.y } else { delta.x * offset.x };
+    (steps > 0 && delta == offset * steps).then_some(steps)
+}
+
+/// Checks if the guard, starting from `guard`, gets stuck in a loop once an
+/// obstruction is placed at `obstruction`.
+///
+/// The guard jumps from one obstacle to the next with the jump table; the new
+/// obstruction is only checked when it lies on the segment being walked.
+fn loops_with_obstruction(
+    jumps: &JumpTable,
+    states: &mut StateSet,
+    obstruction: Position,
+    guard: &Guard,
+) -> bool {
+    let mut position = guard.position;
+    let mut direction = guard.direction;
+
+    loop {
+        let stop = jumps.stop(position, direction);
+
+        // The new obstruction stops the guard earlier if it lies before the next obstacle
+        let stop = match distance_ahead(position, obstruction, direction) {
+            Some(steps) if stop.is_none_or(|stop| {
+                distance_ahead(position, stop, direction).unwrap_or(0) >= steps
+            }) => Some(position + direction.offset() * (steps - 1)),
+            _ => stop,
+        };
+
+        // Guard left the mapped area
+        let Some(stop) = stop else {
+            return false;
+        };
+
+        // We have encountered this state before -> loop detected
+        if !states.insert(jumps.cell(stop) * 4 + direction_index(direction)) {
+            return true;
+        }
+
+        position = stop;
+        direction = direction.turn_right();
+    }
+}
+
+/// Function to place a single new obstruction in different positions and checks if it causes the guard to get stuck in a loop.
+///
+/// Only the cells of the original patrol are tried, and every simulation
+/// resumes from the step before the guard would first hit the obstruction.
+/// With the `parallel` feature the candidates are spread over a thread pool.
+///
+/// If given, `progress` is advanced after every candidate, and finished when
+/// the search returns or panics.
+pub fn count_obstructions_that_cause_loop(grid: &Grid<char>, guard: Position, progress: Option<&Progress>) -> usize {
+    let _finish = progress.map(Progress::finish_on_drop);
+    let jumps = JumpTable::new(grid);
+    let candidates = patrol_candidates(grid, guard);
+    let state_count = grid.width() * grid.height() * 4;
+
+    if let Some(progress) = progress {
+        progress.start(candidates.len());
+    }
+
+    // Checks one candidate, using `states` as scratch space
+    let check = |states: &mut StateSet, candidate: &Candidate| {
+        let in_loop = loops_with_obstruction(&jumps, states, candidate.cell, &candidate.guard);
+        states.clear();
+        if let Some(progress) = progress {
+            progress.advance();
+        }
+        in_loop
+    };
+
+    #[cfg(feature = "parallel")]
+    let loop_count = {
+        use rayon::prelude::*;
+
+        // Every worker thread gets its own scratch state set
+        candidates
+            .par_iter()
+            .map_init(|| StateSet::new(state_count), check)
+            .filter(|&in_loop| in_loop)
+            .count()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let loop_count = {
+        let mut states = StateSet::new(state_count);
+        candidates
+            .iter()
+            .filter(|candidate| check(&mut states, candidate))
+            .count()
+    };
+
+    loop_count
+}
+
+/// Lists the cells where a single new obstruction causes the guard to get
+/// stuck in a loop, in the order the guard first walks over them.
+pub fn obstructions_that_cause_loop(grid: &Grid<char>, guard: Position) -> Vec<Position> {
+    let jumps = JumpTable::new(grid);
+    let mut states = StateSet::new(grid.width() * grid.height() * 4);
+
+    patrol_candidates(grid, guard)
+        .into_iter()
+        .filter(|candidate| {
+            let in_loop = loops_with_obstruction(&jumps, &mut states, candidate.cell, &candidate.guard);
+            states.clear();
+            in_loop
+        })
+        .map(|candidate| candidate.cell)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulate_patrol;
+
+    /// Small labs of up to 8 x 8 cells with about 20% of obstacles, and the guard on a free cell
+    fn labs() -> Vec<(Grid<char>, Position)> {
+        // A small linear congruential generator, so the labs are always the same
+        let mut seed = 2024u64;
+        let mut next = |bound: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+
+        (0..500)
+            .map(|_| {
+                let (width, height) = (1 + next(8) as usize, 1 + next(8) as usize);
+                let rows: Vec<Vec<char>> = (0..height)
+                    .map(|_| (0..width).map(|_| if next(5) == 0 { '#' } else { '.' }).collect())
+                    .collect();
+                let mut grid = Grid::from_rows(rows).unwrap();
+                let guard = Position::new(next(width as u64) as isize, next(height as u64) as isize);
+                grid[guard] = '^';
+                (grid, guard)
+            })
+            .collect()
+    }
+
+    /// Where the guard stops walking straight from `position`, one step at a time
+    fn walk_to_obstacle(grid: &Grid<char>, mut position: Position, direction: Direction) -> Option<Position> {
+        loop {
+            match grid.get(position.step(direction)) {
+                None => return None,
+                Some('#') => return Some(position),
+                Some(_) => position = position.step(direction),
+            }
+        }
+    }
+
+    #[test]
+    fn jumps_stop_where_walking_stops() {
+        for (grid, _) in labs() {
+            let jumps = JumpTable::new(&grid);
+            for (position, _) in grid.iter().filter(|&(_, &cell)| cell != '#') {
+                for direction in Direction::ORTHOGONAL {
+                    let walked = walk_to_obstacle(&grid, position, direction);
+                    assert_eq!(jumps.stop(position, direction), walked, "{:?} {:?} in {:?}", position, direction, grid);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn candidates_are_the_cells_of_the_patrol() {
+        for (grid, start) in labs() {
+            let (visited, _, _) = simulate_patrol(&grid, start);
+            let candidates = patrol_candidates(&grid, start);
+            assert_eq!(candidates.len(), visited - 1, "{:?}", grid);
+
+            for candidate in &candidates {
+                assert_ne!(candidate.cell, start);
+                assert_eq!(candidate.guard.position.step(candidate.guard.direction), candidate.cell);
+            }
+        }
+    }
+
+    #[test]
+    fn obstructions_match_a_brute_force_search() {
+        let mut loops = 0;
+        for (grid, start) in labs() {
+            // As in the puzzle, the guard leaves the lab when nothing is added
+            let (_, leaves_grid, _) = simulate_patrol(&grid, start);
+            if !leaves_grid {
+                continue;
+            }
+
+            let mut expected = Vec::new();
+            for (position, _) in grid.iter().filter(|&(position, &cell)| cell == '.' && position != start) {
+                let mut blocked = grid.clone();
+                blocked[position] = '#';
+                let (_, _, in_loop) = simulate_patrol(&blocked, start);
+                if in_loop {
+                    expected.push(position);
+                }
+            }
+
+            let mut found = obstructions_that_cause_loop(&grid, start);
+            found.sort_unstable_by_key(|position| (position.y, position.x));
+            assert_eq!(found, expected, "{:?}", grid);
+            assert_eq!(count_obstructions_that_cause_loop(&grid, start, None), expected.len());
+            loops += expected.len();
+        }
+
+        // The labs are varied enough to have some obstructions that cause a loop
+        assert!(loops > 0);
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Thread-safe progress counter for the obstruction search.
+///
+/// Workers call `advance` after every candidate; any other thread can read
+/// the counter at the same time, e.g. to print it with `print_until_done`.
+#[derive(Debug, Default)]
+pub struct Progress {
+    processed: AtomicUsize, // Candidates checked so far
+    total: AtomicUsize,     // Candidates to check, known once the search starts
+    done: AtomicBool,       // Set when the search has finished
+}
+
+impl Progress {
+    /// Creates a counter with nothing processed yet.
+    pub fn new() -> Self {
+        Progress::default()
+    }
+
+    /// Number of candidates checked so far.
+    pub fn processed(&self) -> usize {
+        self.processed.load(Ordering::Relaxed)
+    }
+
+    /// Number of candidates to check, or 0 if the search has not started yet.
+    pub fn total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    /// Checks if the search has finished.
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+
+    /// Percentage of the candidates checked so far.
+    pub fn percentage(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => (self.processed() as f64 / total as f64) * 100.0,
+        }
+    }
+
+    /// Prints the progress to stderr every `interval` until the search is done.
+    ///
+    /// Meant to run on its own thread while the search runs on others.
+    pub fn print_until_done(&self, interval: Duration) {
+        while !self.is_done() {
+            eprint!("\rProcessing: {:.2}% ", self.percentage());
+            io::stderr().flush().ok();
+            thread::sleep(interval);
+        }
+        // Print a newline after finishing progress
+        eprintln!("\rProcessing: {:.2}% ", self.percentage());
+    }
+
+    pub(crate) fn start(&self, total: usize) {
+        self.processed.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn advance(&self) {
+        self.processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish(&self) {
+        self.done.store(true, Ordering::Release);
+    }
+
+    /// Finishes the search when the returned guard is dropped, even by a panic,
+    /// so that `print_until_done` never waits for a search that died
+    pub(crate) fn finish_on_drop(&self) -> FinishOnDrop<'_> {
+        FinishOnDrop(self)
+    }
+}
+
+/// Guard returned by [`Progress::finish_on_drop`]
+pub(crate) struct FinishOnDrop<'a>(&'a Progress);
+
+impl Drop for FinishOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use aoc_common::{Grid, Position};
+
+    use super::*;
+    use crate::count_obstructions_that_cause_loop;
+
+    #[test]
+    fn a_search_that_panics_still_finishes() {
+        let grid = Grid::filled(3, 3, '.');
+        let progress = Progress::new();
+
+        // A guard outside of the map makes the search panic
+        let search = panic::catch_unwind(|| count_obstructions_that_cause_loop(&grid, Position::new(5, 5), Some(&progress)));
+        assert!(search.is_err());
+        assert!(progress.is_done());
+    }
+
+    #[test]
+    fn progress_counts_every_candidate() {
+        let grid = Grid::parse("....\n.#..\n..^.\n....\n", |c| c).unwrap();
+        let progress = Progress::new();
+        assert_eq!(progress.percentage(), 0.0);
+
+        count_obstructions_that_cause_loop(&grid, Position::new(2, 2), Some(&progress));
+        assert!(progress.is_done());
+        assert_eq!(progress.total(), 2);
+        assert_eq!(progress.processed(), 2);
+        assert_eq!(progress.percentage(), 100.0);
+    }
+}
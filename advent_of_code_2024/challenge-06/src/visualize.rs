@@ -0,0 +1,412 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::{Direction, Grid, Position};
+
+use crate::loop_search::direction_index;
+use crate::{Guard, Lab};
+
+/// What is drawn in a single cell of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    /// Free floor the guard has not walked on yet
+    Empty,
+    /// An obstacle of the original map
+    Obstacle,
+    /// The extra obstruction placed on the map
+    Obstruction,
+    /// Floor the guard has already walked on
+    Visited,
+    /// The guard itself, facing the given direction
+    Guard(Direction),
+}
+
+impl Tile {
+    /// Every tile kind, in the order of their palette entries
+    const PALETTE: [[u8; 3]; 5] = [
+        [24, 24, 32],    // Empty
+        [128, 128, 136], // Obstacle
+        [220, 48, 48],   // Obstruction
+        [56, 104, 200],  // Visited
+        [248, 208, 48],  // Guard
+    ];
+
+    /// Index of the tile's colour in `PALETTE`
+    fn palette_index(self) -> u8 {
+        match self {
+            Tile::Empty => 0,
+            Tile::Obstacle => 1,
+            Tile::Obstruction => 2,
+            Tile::Visited => 3,
+            Tile::Guard(_) => 4,
+        }
+    }
+
+    /// RGB colour of the tile in exported images
+    fn rgb(self) -> [u8; 3] {
+        Tile::PALETTE[self.palette_index() as usize]
+    }
+
+    /// Character and ANSI colour code used to draw the tile in a terminal
+    fn ansi(self) -> (char, &'static str) {
+        match self {
+            Tile::Empty => ('.', "2"),
+            Tile::Obstacle => ('#', "90"),
+            Tile::Obstruction => ('O', "1;31"),
+            Tile::Visited => ('X', "34"),
+            Tile::Guard(direction) => (arrow(direction), "1;33"),
+        }
+    }
+}
+
+/// Arrow pointing in the direction the guard is facing
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        _ => unreachable!("the guard only moves orthogonally"),
+    }
+}
+
+/// How a recorded patrol ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The guard walked off the map
+    LeftMap,
+    /// The guard came back to a state it had already been in
+    Loop,
+}
+
+/// Error returned when the extra obstruction cannot be placed where asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObstructionError {
+    /// The position is not on the map
+    OutsideMap(Position),
+    /// There is already an obstacle at the position
+    OnObstacle(Position),
+    /// The guard stands at the position when the patrol starts
+    OnGuard(Position),
+}
+
+impl fmt::Display for ObstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObstructionError::OutsideMap(at) => write!(f, "obstruction {},{} is outside the map", at.x, at.y),
+            ObstructionError::OnObstacle(at) => write!(f, "obstruction {},{} is on an obstacle", at.x, at.y),
+            ObstructionError::OnGuard(at) => write!(f, "obstruction {},{} is on the guard's starting position", at.x, at.y),
+        }
+    }
+}
+
+impl Error for ObstructionError {}
+
+/// A snapshot of the lab at one step of the patrol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    step: usize,       // Number of steps taken before this frame
+    tiles: Grid<Tile>, // What to draw in every cell
+}
+
+impl Frame {
+    /// Number of steps the guard has taken before this frame
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The tiles of the frame
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    /// Draws the frame as text with ANSI colours, one line per row
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.tiles.rows() {
+            for &tile in row {
+                let (ch, colour) = tile.ansi();
+                write!(text, "\x1b[{}m{}\x1b[0m", colour, ch).unwrap();
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Size in pixels of the frame when every cell is drawn as a `scale` x `scale` square
+    pub fn image_size(&self, scale: usize) -> (usize, usize) {
+        (self.tiles.width() * scale, self.tiles.height() * scale)
+    }
+
+    /// Draws the frame as one value per pixel, row by row, with every cell
+    /// drawn as a `scale` x `scale` square
+    fn pixels<T: Copy>(&self, scale: usize, pixel: impl Fn(Tile) -> T) -> Vec<T> {
+        let (width, height) = self.image_size(scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in self.tiles.rows() {
+            for _ in 0..scale {
+                for &tile in row {
+                    pixels.extend(std::iter::repeat_n(pixel(tile), scale));
+                }
+            }
+        }
+        pixels
+    }
+
+    /// Writes the frame as a binary PPM (P6) image
+    pub fn write_ppm<W: Write>(&self, mut writer: W, scale: usize) -> io::Result<()> {
+        let (width, height) = self.image_size(scale);
+        write!(writer, "P6\n{} {}\n255\n", width, height)?;
+        writer.write_all(&self.pixels(scale, Tile::rgb).concat())?;
+        writer.flush()
+    }
+}
+
+/// The patrol of the guard, recorded step by step.
+///
+/// Frames are rebuilt on demand from the recorded steps, so even long patrols
+/// only keep one frame in memory at a time.
+pub struct Recording {
+    start: Grid<Tile>,                 // The lab before the guard moves, without the guard
+    steps: Vec<(Position, Direction)>, // The guard's state after every step, starting with the initial one
+    outcome: Outcome,                  // How the patrol ends
+}
+
+impl Recording {
+    /// Simulates the patrol in `lab`, optionally with an extra obstruction
+    /// at `obstruction`, recording every step until the guard walks off the
+    /// map or gets stuck in a loop.
+    ///
+    /// The obstruction must go on a free cell of the map other than the guard's.
+    pub fn record(lab: &Lab, obstruction: Option<Position>) -> Result<Self, ObstructionError> {
+        let mut grid = lab.grid.clone();
+        let mut start = lab.grid.map(|&ch| if ch == '#' { Tile::Obstacle } else { Tile::Empty });
+        if let Some(obstruction) = obstruction {
+            match grid.get_mut(obstruction) {
+                None => return Err(ObstructionError::OutsideMap(obstruction)),
+                Some('#') => return Err(ObstructionError::OnObstacle(obstruction)),
+                Some(_) if obstruction == lab.guard => return Err(ObstructionError::OnGuard(obstruction)),
+                Some(cell) => *cell = '#',
+            }
+            start[obstruction] = Tile::Obstruction;
+        }
+
+        let mut guard = Guard::new(lab.guard, Direction::Up);
+        let mut seen = grid.map(|_| [false; 4]);
+        let mut steps = vec![(guard.position, guard.direction)];
+        seen[guard.position][direction_index(guard.direction)] = true;
+
+        let outcome = loop {
+            let next_direction = guard.next_direction(&grid);
+
+            // If the direction remains unchanged, move forward
+            if next_direction == guard.direction {
+                guard.move_forward();
+            }
+            guard.direction = next_direction;
+
+            // Guard left the mapped area
+            if !grid.in_bounds(guard.position) {
+                break Outcome::LeftMap;
+            }
+            steps.push((guard.position, guard.direction));
+
+            // We have encountered this state before -> loop detected
+            let seen = &mut seen[guard.position][direction_index(guard.direction)];
+            if *seen {
+                break Outcome::Loop;
+            }
+            *seen = true;
+        };
+
+        Ok(Recording { start, steps, outcome })
+    }
+
+    /// How the patrol ends
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    /// Number of frames: one for the initial state and one per step
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Checks if there are no frames at all
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Iterates over the frames in order, from the initial state to the last step
+    pub fn frames(&self) -> impl Iterator<Item = Frame> + '_ {
+        let mut tiles = self.start.clone();
+        let mut previous: Option<Position> = None;
+
+        self.steps.iter().enumerate().map(move |(step, &(position, direction))| {
+            if let Some(previous) = previous {
+                tiles[previous] = Tile::Visited;
+            }
+            tiles[position] = Tile::Guard(direction);
+            previous = Some(position);
+            Frame { step, tiles: tiles.clone() }
+        })
+    }
+
+    /// Plays the patrol in the terminal, redrawing the screen every `delay`
+    pub fn play<W: Write>(&self, mut writer: W, delay: Duration) -> io::Result<()> {
+        let total = self.len().saturating_sub(1);
+        for frame in self.frames() {
+            // Move the cursor home and clear the screen before every frame
+            write!(writer, "\x1b[H\x1b[2J{}", frame.to_ansi())?;
+            writeln!(writer, "Step {}/{}", frame.step(), total)?;
+            writer.flush()?;
+            thread::sleep(delay);
+        }
+        match self.outcome {
+            Outcome::LeftMap => writeln!(writer, "The guard left the map"),
+            Outcome::Loop => writeln!(writer, "The guard is stuck in a loop"),
+        }
+    }
+
+    /// Writes the patrol as an animated GIF, showing every frame for `delay`
+    /// and drawing every cell as a `scale` x `scale` square
+    pub fn write_gif<W: Write>(&self, writer: W, scale: usize, delay: Duration) -> io::Result<()> {
+        let (width, height) = (self.start.width() * scale, self.start.height() * scale);
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "the image is too large for a GIF");
+        let width = u16::try_from(width).map_err(|_| too_large())?;
+        let height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let palette = Tile::PALETTE.concat();
+        let mut encoder = gif::Encoder::new(writer, width, height, &palette).map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+
+        // GIF delays are in hundredths of a second
+        let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+        for frame in self.frames() {
+            let gif_frame = gif::Frame {
+                width,
+                height,
+                delay,
+                buffer: Cow::Owned(frame.pixels(scale, Tile::palette_index)),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::Solution;
+
+    use super::*;
+    use crate::Day06;
+
+    fn example() -> Lab {
+        Day06::parse(include_str!("../examples/example.txt")).unwrap()
+    }
+
+    #[test]
+    fn the_last_frame_shows_every_visited_cell() {
+        let lab = example();
+        let recording = Recording::record(&lab, None).unwrap();
+        assert_eq!(recording.outcome(), Outcome::LeftMap);
+
+        let frames: Vec<Frame> = recording.frames().collect();
+        assert_eq!(frames.len(), recording.len());
+        assert_eq!(frames[0].tiles()[lab.guard()], Tile::Guard(Direction::Up));
+        assert!(frames[0].tiles().iter().all(|(_, &tile)| tile != Tile::Visited));
+
+        // The guard's own cell counts as visited too
+        let last = frames.last().unwrap();
+        let visited = last.tiles().iter().filter(|&(_, &tile)| matches!(tile, Tile::Visited | Tile::Guard(_))).count();
+        assert_eq!(visited, 41);
+        assert_eq!(last.step(), recording.len() - 1);
+    }
+
+    #[test]
+    fn obstructions_are_drawn_and_can_cause_a_loop() {
+        let lab = example();
+        let obstruction = Position::new(3, 6);
+        let recording = Recording::record(&lab, Some(obstruction)).unwrap();
+        assert_eq!(recording.outcome(), Outcome::Loop);
+        assert!(recording.frames().all(|frame| frame.tiles()[obstruction] == Tile::Obstruction));
+    }
+
+    #[test]
+    fn obstructions_must_go_on_a_free_cell() {
+        let lab = example();
+        for (obstruction, error) in [
+            (Position::new(10, 0), ObstructionError::OutsideMap(Position::new(10, 0))),
+            (Position::new(4, 0), ObstructionError::OnObstacle(Position::new(4, 0))),
+            (lab.guard(), ObstructionError::OnGuard(lab.guard())),
+        ] {
+            assert_eq!(Recording::record(&lab, Some(obstruction)).err(), Some(error));
+        }
+        assert_eq!(
+            ObstructionError::OnGuard(lab.guard()).to_string(),
+            "obstruction 4,6 is on the guard's starting position"
+        );
+    }
+
+    #[test]
+    fn frames_are_drawn_as_text_and_ppm() {
+        let lab = Day06::parse("#.\n.^\n").unwrap();
+        let frame = Recording::record(&lab, None).unwrap().frames().next().unwrap();
+        assert_eq!(frame.to_ansi(), "\x1b[90m#\x1b[0m\x1b[2m.\x1b[0m\n\x1b[2m.\x1b[0m\x1b[1;33m^\x1b[0m\n");
+
+        let mut ppm = Vec::new();
+        frame.write_ppm(&mut ppm, 2).unwrap();
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+
+        // Every cell is a 2 x 2 square; the obstacle is the top left one
+        let pixels: Vec<&[u8]> = ppm[header.len()..].chunks(3).collect();
+        assert_eq!(pixels.len(), 16);
+        for (i, pixel) in pixels.iter().enumerate() {
+            let cell = Position::new((i % 4 / 2) as isize, (i / 4 / 2) as isize);
+            assert_eq!(*pixel, frame.tiles()[cell].rgb(), "pixel {}", i);
+        }
+    }
+
+    #[test]
+    fn gifs_have_one_frame_per_step() {
+        let lab = example();
+        let recording = Recording::record(&lab, None).unwrap();
+        let mut gif = Vec::new();
+        recording.write_gif(&mut gif, 3, Duration::from_millis(50)).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (30, 30));
+        // GIF palettes are padded to a power of two entries
+        let palette = Tile::PALETTE.concat();
+        assert_eq!(decoder.global_palette().map(|colours| &colours[..palette.len()]), Some(palette.as_slice()));
+
+        let mut frames = 0;
+        let mut last = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 5);
+            last = frame.buffer.to_vec();
+            frames += 1;
+        }
+        assert_eq!(frames, recording.len());
+
+        let expected = recording.frames().last().unwrap().pixels(3, Tile::palette_index);
+        assert_eq!(last, expected);
+    }
+
+    #[test]
+    fn gifs_too_large_are_rejected() {
+        let lab = example();
+        let recording = Recording::record(&lab, None).unwrap();
+        let error = recording.write_gif(io::sink(), 10_000, Duration::ZERO).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::operator::OperatorSet;
+use crate::solver::find_forwards;
+use crate::value::Value;
+use crate::Equation;
+
+/// Whether an equation can be made true, and how
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Verdict {
+    /// The equation has at least one solution
+    Solved {
+        expression: String, // The first solution found, e.g. `292 = 11 + 6 * 16 + 20`
+        solutions: usize,   // Number of distinct operator sequences that work
+    },
+    /// No operator sequence works
+    Unsolvable { reason: String },
+}
+
+/// The verdict for one equation of the input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation<V: Value> {
+    pub line: usize,     // Line of the equation in the input, from 1
+    pub target: V,       // The value the numbers must combine into
+    pub numbers: Vec<V>, // The numbers to combine, in order
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+impl<V: Value> fmt::Display for Explanation<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.verdict {
+            Verdict::Solved { expression, solutions: 1 } => {
+                write!(f, "line {}: {} (1 solution)", self.line, expression)
+            }
+            Verdict::Solved { expression, solutions } => {
+                write!(f, "line {}: {} ({} solutions)", self.line, expression, solutions)
+            }
+            Verdict::Unsolvable { reason } => write!(f, "line {}: {} is unsolvable: {}", self.line, self.target, reason),
+        }
+    }
+}
+
+/// Finds every operator sequence that makes the equation true, each listed
+/// as operator symbols from left to right
+pub fn solutions<'a, V: Value>(target: V, numbers: &[V], operators: &'a OperatorSet<V>) -> Vec<Vec<&'a str>> {
+    let mut found = Vec::new();
+    search(target, numbers, operators, &mut Vec::new(), &mut found);
+    found
+}
+
+/// Same search as `is_solvable`, but it keeps going after the first solution
+/// and remembers the operators used, starting from the last one
+fn search<'a, V: Value>(
+    target: V,
+    numbers: &[V],
+    operators: &'a OperatorSet<V>,
+    suffix: &mut Vec<&'a str>,
+    found: &mut Vec<Vec<&'a str>>,
+) {
+    match numbers {
+        [] => {}
+        [first] => {
+            if *first == target {
+                found.push(suffix.iter().rev().copied().collect());
+            }
+        }
+        [.., last] if *last == V::ZERO => {
+            find_forwards(numbers, operators, &mut |value, symbols| {
+                if value == target {
+                    found.push(symbols.iter().chain(suffix.iter().rev()).copied().collect());
+                }
+                false // Keep looking for more solutions
+            });
+        }
+        [rest @ .., last] => {
+            for operator in operators.iter() {
+                if let Some(previous) = operator.invert(target, *last) {
+                    suffix.push(operator.symbol());
+                    search(previous, rest, operators, suffix, found);
+                    suffix.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Writes the equation with the given operators, e.g. `292 = 11 + 6 * 16 + 20`
+fn format_expression<V: Value>(target: V, numbers: &[V], symbols: &[&str]) -> String {
+    let mut expression = format!("{} = {}", target, numbers[0]);
+    for (symbol, number) in symbols.iter().zip(&numbers[1..]) {
+        expression.push_str(&format!(" {} {}", symbol, number));
+    }
+    expression
+}
+
+/// Tells why no operator sequence makes the equation true
+fn unsolvable_reason<V: Value>(target: V, numbers: &[V], operators: &OperatorSet<V>) -> String {
+    match numbers {
+        [] => "there are no numbers to combine".to_string(),
+        [only] => format!("the only number is {}", only),
+        [.., last] if *last != V::ZERO && operators.iter().all(|operator| operator.invert(target, *last).is_none()) => {
+            format!("no operator can end with {} and give {}", last, target)
+        }
+        _ => match operators.len().checked_pow(numbers.len() as u32 - 1) {
+            Some(combinations) => format!("none of the {} operator combinations gives {}", combinations, target),
+            None => format!("no operator combination gives {}", target),
+        },
+    }
+}
+
+/// Explains one equation: its first solution and how many there are, or
+/// why it cannot be solved
+pub fn explain<V: Value>(line: usize, equation: &Equation<V>, operators: &OperatorSet<V>) -> Explanation<V> {
+    let Equation { target, numbers } = equation;
+    let found = solutions(*target, numbers, operators);
+
+    let verdict = match found.first() {
+        Some(symbols) => Verdict::Solved {
+            expression: format_expression(*target, numbers, symbols),
+            solutions: found.len(),
+        },
+        None => Verdict::Unsolvable { reason: unsolvable_reason(*target, numbers, operators) },
+    };
+
+    Explanation { line, target: *target, numbers: numbers.clone(), verdict }
+}
+
+/// Explains every equation, numbering them by input line
+pub fn explain_all<V: Value>(equations: &[Equation<V>], operators: &OperatorSet<V>) -> Vec<Explanation<V>> {
+    equations
+        .iter()
+        .enumerate()
+        .map(|(index, equation)| explain(index + 1, equation, operators))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::parse_equations;
+
+    fn example() -> Vec<Equation<u64>> {
+        parse_equations(include_str!("../examples/example.txt")).unwrap()
+    }
+
+    #[test]
+    fn every_line_gets_its_solution_count() {
+        let equations = example();
+        let counts = |operators: &OperatorSet<u64>| -> Vec<(usize, usize)> {
+            explain_all(&equations, operators)
+                .iter()
+                .map(|explanation| match explanation.verdict {
+                    Verdict::Solved { solutions, .. } => (explanation.line, solutions),
+                    Verdict::Unsolvable { .. } => (explanation.line, 0),
+                })
+                .collect()
+        };
+
+        assert_eq!(counts(&OperatorSet::part1()), [(1, 1), (2, 2), (3, 0), (4, 0), (5, 0), (6, 0), (7, 0), (8, 0), (9, 1)]);
+        assert_eq!(counts(&OperatorSet::part2()), [(1, 1), (2, 2), (3, 0), (4, 1), (5, 1), (6, 0), (7, 1), (8, 0), (9, 1)]);
+    }
+
+    #[test]
+    fn explanations_read_like_the_equations() {
+        let equations = example();
+        let text: Vec<String> = explain_all(&equations, &OperatorSet::part2()).iter().map(|e| e.to_string()).collect();
+        assert_eq!(text[0], "line 1: 190 = 10 * 19 (1 solution)");
+        assert_eq!(text[1], "line 2: 3267 = 81 * 40 + 27 (2 solutions)");
+        assert_eq!(text[2], "line 3: 83 is unsolvable: none of the 3 operator combinations gives 83");
+        assert_eq!(text[4], "line 5: 7290 = 6 * 8 || 6 * 15 (1 solution)");
+        assert_eq!(text[5], "line 6: 161011 is unsolvable: none of the 9 operator combinations gives 161011");
+    }
+
+    #[test]
+    fn unsolvable_equations_say_why() {
+        let operators = OperatorSet::<u64>::part1();
+        let reason = |target, numbers: &[u64]| match explain(1, &Equation { target, numbers: numbers.to_vec() }, &operators).verdict {
+            Verdict::Unsolvable { reason } => reason,
+            verdict => panic!("{:?} is solvable: {:?}", numbers, verdict),
+        };
+
+        assert_eq!(reason(5, &[4]), "the only number is 4");
+        assert_eq!(reason(7, &[2, 9]), "no operator can end with 9 and give 7");
+        assert_eq!(reason(5, &[3, 0]), "none of the 2 operator combinations gives 5");
+        assert_eq!(reason(3, &[2; 70]), "no operator combination gives 3");
+    }
+
+    #[test]
+    fn explanations_serialize_to_flat_json() {
+        let equations = example();
+        let explanations = explain_all(&equations[..3], &OperatorSet::part1());
+        assert_eq!(
+            serde_json::to_value(&explanations).unwrap(),
+            json!([
+                { "line": 1, "target": 190, "numbers": [10, 19], "status": "solved", "expression": "190 = 10 * 19", "solutions": 1 },
+                { "line": 2, "target": 3267, "numbers": [81, 40, 27], "status": "solved", "expression": "3267 = 81 * 40 + 27", "solutions": 2 },
+                {
+                    "line": 3,
+                    "target": 83,
+                    "numbers": [17, 5],
+                    "status": "unsolvable",
+                    "reason": "none of the 2 operator combinations gives 83"
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,127 @@
+pub mod explain;
+pub mod operator;
+pub mod solver;
+pub mod value;
+
+use std::fmt;
+
+use aoc_common::parse::parse_numbers;
+use aoc_common::solution::{Answer, Error, Solution};
+
+pub use operator::{Builtin, Operator, OperatorSet};
+pub use solver::{evaluate, is_solvable, total_calibration, EvalError};
+pub use value::Value;
+
+/// The integer type the puzzle is solved with: `u128` with the `u128`
+/// feature, for inputs whose values do not fit in 64 bits
+#[cfg(feature = "u128")]
+pub type Number = u128;
+#[cfg(not(feature = "u128"))]
+pub type Number = u64;
+
+/// A calibration equation with its operators missing, e.g. `190: 10 19`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation<V: Value = Number> {
+    pub target: V,       // The value the numbers must combine into
+    pub numbers: Vec<V>, // The numbers to combine, in order
+}
+
+/// Parses one equation per line, e.g. `190: 10 19`.
+///
+/// Blank lines after the last equation are ignored; errors name the line of the input.
+pub fn parse_equations<V: Value>(input: &str) -> Result<Vec<Equation<V>>, Error>
+where
+    V::Err: fmt::Display,
+{
+    let mut equations = Vec::new();
+
+    // Leave out the blank lines at the end of the input
+    let lines: Vec<&str> = input.lines().collect();
+    let end = lines.iter().rposition(|text| !text.trim().is_empty()).map_or(0, |last| last + 1);
+
+    for (index, text) in lines[..end].iter().enumerate() {
+        let line = index + 1;
+        let Some((target, numbers)) = text.split_once(':') else {
+            return Err(format!("line {}: expected '<total>: <numbers>', found {:?}", line, text).into());
+        };
+        let target = target
+            .trim()
+            .parse::<V>()
+            .map_err(|e| format!("line {}: {:?} is not a valid total: {}", line, target.trim(), e))?;
+        let numbers: Vec<V> = parse_numbers(numbers).map_err(|e| format!("line {}: {}", line, e))?;
+        if numbers.is_empty() {
+            return Err(format!("line {}: no numbers to combine in {:?}", line, text).into());
+        }
+
+        equations.push(Equation { target, numbers });
+    }
+
+    Ok(equations)
+}
+
+/// Solver for Day 7: Bridge Repair
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Vec<Equation>, Error> {
+        parse_equations(input)
+    }
+
+    fn part1(equations: &Vec<Equation>) -> Result<Answer, Error> {
+        Ok(total_calibration(equations, &OperatorSet::part1())?.to_answer())
+    }
+
+    fn part2(equations: &Vec<Equation>) -> Result<Answer, Error> {
+        Ok(total_calibration(equations, &OperatorSet::part2())?.to_answer())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Vec<Equation<u64>>, String> {
+        parse_equations(input).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored() {
+        let equations = parse("190: 10 19\n\n \n").unwrap();
+        assert_eq!(equations, [Equation { target: 190, numbers: vec![10, 19] }]);
+        assert_eq!(parse(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        assert_eq!(parse("190: 10 19\n\n3267: 81 40 27\n"), Err("line 2: expected '<total>: <numbers>', found \"\"".to_string()));
+        assert_eq!(
+            parse("190: 10 19\n3267: 81 x 27\n"),
+            Err("line 2: \"x\" is not a valid number: invalid digit found in string".to_string())
+        );
+        assert_eq!(
+            parse("99999999999999999999: 1\n"),
+            Err("line 1: \"99999999999999999999\" is not a valid total: number too large to fit in target type".to_string())
+        );
+        assert_eq!(parse("7: 7\n190:\n"), Err("line 2: no numbers to combine in \"190:\"".to_string()));
+    }
+
+    #[test]
+    fn equations_and_operators_default_to_the_same_number_type() {
+        let equation: Equation = Equation { target: 3267, numbers: vec![81, 40, 27] };
+        let operators: OperatorSet = OperatorSet::part1();
+        assert!(is_solvable(equation.target, &equation.numbers, &operators));
+    }
+
+    #[cfg(feature = "u128")]
+    #[test]
+    fn the_u128_feature_solves_past_u64() {
+        let equations = Day07::parse("123456789012345678901: 12345678901234567890 1\n").unwrap();
+        assert_eq!(Day07::part1(&equations).unwrap(), Answer::Number(0));
+        assert_eq!(Day07::part2(&equations).unwrap(), Answer::Number(123_456_789_012_345_678_901));
+    }
+}
@@ -1,121 +1,56 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::path::Path;
-use std::io::{self, BufRead};
-use itertools::Itertools;
-
-
-// Generate all possible sequences of operators
-fn generate_operator_sequences(length: usize) -> Vec<Vec<char>> {
-    let operators = vec!['+', '*', '|'];
-    if length == 0 {
-        return vec![];
-    }
-    (0..length)
-        .map(|_| operators.clone())
-        .multi_cartesian_product()
-        .collect()
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_common::input::DEFAULT_INPUT;
+use aoc_common::solution::{run_main, run_with, Error};
+use challenge_07::explain::explain_all;
+use challenge_07::{Day07, Equation, OperatorSet};
+use clap::Parser;
+
+/// Solves day 7, or explains how every equation is solved.
+#[derive(Parser)]
+#[command(name = "challenge-07")]
+struct Cli {
+    /// Puzzle input file
+    #[arg(default_value = DEFAULT_INPUT)]
+    input: PathBuf,
+
+    /// Print the satisfying expression of every equation, or why there is none
+    #[arg(long)]
+    explain: bool,
+
+    /// Print the explanations as JSON
+    #[arg(long, requires = "explain")]
+    json: bool,
+
+    /// Operators to explain with: 1 for `+ *`, 2 for `+ * ||`
+    #[arg(long, default_value_t = 2, requires = "explain", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
 }
 
-/// Reads lines from a file and returns an iterator over them.
-/// If the file cannot be opened, an error is returned.
-///
-/// # Arguments
-/// * `filename` - Path to the file to be read.
-///
-/// # Returns
-/// * An iterator over the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn calculate_matching_expressions(rows: Vec<Vec<i64>>) -> i64 {
-
-    let mut valid_expressions_total: i64 = 0;
-    let mut hash_map:HashMap<usize, Vec<Vec<char>>> = HashMap::new();
-
-    for row in rows.iter() {
-        
-        // The first value in the sequence represents the target total
-        let target_total = row[0];
-        // The remaining values are the numbers to operate on
-        let numbers = &row[1..];
-        // Store the number of operators to produce
-        let operators_length = numbers.len()-1;
-
-        let operator_sequences: Vec<Vec<char>>;
-
-        // Get the sequence of operators if already present in the hashMap
-        if hash_map.contains_key(&operators_length) {
-            operator_sequences = hash_map.get(&operators_length).unwrap().to_vec();
-        } else {
-            // Generate all possible operator sequences ((len(n)-1)-long)
-            operator_sequences = generate_operator_sequences(operators_length);
-            hash_map.insert(operators_length, operator_sequences.clone());
-        }
-
-        for operators in operator_sequences.iter() {
-            let (is_match, matching_value) = evaluate_expression(target_total, numbers,  operators);
-            if is_match {
-                valid_expressions_total += matching_value;
-                // Stop further checks for this row once a match is found
-                break;
-            }
-        }
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if !cli.explain {
+        return run_main::<Day07>(&cli.input);
     }
 
-    valid_expressions_total
+    run_with::<Day07>(&cli.input, |equations| explain(&cli, equations))
 }
 
-fn concat_integers(a: i64, b: i64) -> u64 {
-    a as u64 * 10u64.pow(b.ilog10() + 1) + b as u64
-}
-
-// Evaluate the expression formed by the combination of numbers and operators.
-// Returns a tuple: (whether the result matches the target total, value if it matches)
-fn evaluate_expression(target_total: i64, numbers: &[i64], operators: &[char]) -> (bool, i64) {
-    let mut result = numbers[0];
-    for (op, &num) in operators.iter().zip(&numbers[1..]) {
-        match op {
-            '+' => result += num,
-            '*' => result *= num,
-            '|' => result = concat_integers(result, num) as i64,
-            _ => panic!("Unsupported operator"),
-        }
-    }
-
-    if result == target_total {
-        return (true, target_total);
-    }
-
-    (false, 0)
-}
-
-fn main() {
-    let mut parsed_rows: Vec<Vec<i64>> = Vec::new();
-
-    // Read and parse the input file
-    if let Ok(lines) = read_lines("./puzzle_input.txt") {
-        for line in lines.flatten() {
-            let parts: Vec<&str> = line.split(':').collect();
-            let target_total = parts[0].trim().parse::<i64>().unwrap();
-            let numbers: Vec<i64> = parts[1]
-                .split_whitespace()
-                .filter_map(|s| s.trim().parse::<i64>().ok())
-                .collect();
-
-            let mut row: Vec<i64> = vec![target_total];
-            row.extend(numbers);
-
-            parsed_rows.push(row);
+fn explain(cli: &Cli, equations: &[Equation]) -> Result<(), Error> {
+    let operators = match cli.part {
+        1 => OperatorSet::part1(),
+        _ => OperatorSet::part2(),
+    };
+
+    let explanations = explain_all(equations, &operators);
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&explanations)?);
+    } else {
+        for explanation in &explanations {
+            println!("{}", explanation);
         }
     }
 
-    // Calculate the number of matching expressions
-    let result = calculate_matching_expressions(parsed_rows);
-
-    // Output the result
-    println!("{}", result);
+    Ok(())
 }
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::value::Value;
+use crate::Number;
+
+/// A binary operator that can be placed between two numbers of an equation.
+///
+/// Equations are always evaluated left to right, so an operator combines the
+/// value of everything on its left with the number on its right.
+pub trait Operator<V: Value = Number>: Send + Sync {
+    /// Symbol used when printing an expression, e.g. `+`
+    fn symbol(&self) -> &str;
+
+    /// Combines the value on the left with the number on the right, or
+    /// returns `None` if the result does not fit in `V`
+    fn apply(&self, left: V, right: V) -> Option<V>;
+
+    /// Finds the value on the left that gives `result` when combined with
+    /// `right`, or `None` if there is no such value.
+    ///
+    /// The solver works from the last number backwards, so this is what lets
+    /// it discard a whole branch as soon as an operator cannot fit. A zero
+    /// `right` can leave many values on the left, e.g. `x * 0 = 0` for any
+    /// `x`, so the solver evaluates such branches forwards instead and never
+    /// calls this with a zero `right`.
+    fn invert(&self, result: V, right: V) -> Option<V>;
+}
+
+/// The operators of the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Builtin {
+    /// `+`: addition
+    Add,
+    /// `*`: multiplication
+    Multiply,
+    /// `||`: writes the digits of the right number after the left one, e.g. `12 || 345 = 12345`
+    Concat,
+}
+
+impl<V: Value> Operator<V> for Builtin {
+    fn symbol(&self) -> &str {
+        match self {
+            Builtin::Add => "+",
+            Builtin::Multiply => "*",
+            Builtin::Concat => "||",
+        }
+    }
+
+    fn apply(&self, left: V, right: V) -> Option<V> {
+        match self {
+            Builtin::Add => left.checked_add(right),
+            Builtin::Multiply => left.checked_mul(right),
+            Builtin::Concat => left.checked_mul(right.checked_digit_shift()?)?.checked_add(right),
+        }
+    }
+
+    fn invert(&self, result: V, right: V) -> Option<V> {
+        match self {
+            // Subtract
+            Builtin::Add => result.checked_sub(right),
+            // Divide, if divisible
+            Builtin::Multiply => result.checked_div_exact(right),
+            // Strip the digits of `right` from the end of `result`
+            Builtin::Concat => match right.checked_digit_shift() {
+                Some(shift) => {
+                    let (left, last) = result.split_digits(shift);
+                    (last == right).then_some(left)
+                }
+                // `right` has as many digits as the type allows, so `result` can only be `0 || right`
+                None => (result == right).then_some(V::ZERO),
+            },
+        }
+    }
+}
+
+/// The operators a solver is allowed to use
+pub struct OperatorSet<V: Value = Number> {
+    operators: Vec<Box<dyn Operator<V>>>,
+}
+
+impl<V: Value> Default for OperatorSet<V> {
+    fn default() -> Self {
+        OperatorSet { operators: Vec::new() }
+    }
+}
+
+impl<V: Value> OperatorSet<V> {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        OperatorSet::default()
+    }
+
+    /// The operators of part 1: `+` and `*`
+    pub fn part1() -> Self {
+        OperatorSet::new().with(Builtin::Add).with(Builtin::Multiply)
+    }
+
+    /// The operators of part 2: part 1 plus `||`
+    pub fn part2() -> Self {
+        OperatorSet::part1().with(Builtin::Concat)
+    }
+
+    /// Adds an operator to the set, returning the set
+    pub fn with(mut self, operator: impl Operator<V> + 'static) -> Self {
+        self.register(operator);
+        self
+    }
+
+    /// Adds an operator to the set
+    pub fn register(&mut self, operator: impl Operator<V> + 'static) {
+        self.operators.push(Box::new(operator));
+    }
+
+    /// Looks up an operator by its symbol
+    pub fn get(&self, symbol: &str) -> Option<&dyn Operator<V>> {
+        self.iter().find(|operator| operator.symbol() == symbol)
+    }
+
+    /// Iterates over the operators, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Operator<V>> {
+        self.operators.iter().map(|operator| operator.as_ref())
+    }
+
+    /// Number of operators in the set
+    pub fn len(&self) -> usize {
+        self.operators.len()
+    }
+
+    /// Checks if the set has no operators
+    pub fn is_empty(&self) -> bool {
+        self.operators.is_empty()
+    }
+}
+
+impl<V: Value> fmt::Debug for OperatorSet<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(|operator| operator.symbol())).finish()
+    }
+}
@@ -0,0 +1,210 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::operator::OperatorSet;
+use crate::value::Value;
+use crate::Equation;
+
+/// Error returned when an expression cannot be evaluated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// There are no numbers to combine
+    NoNumbers,
+    /// The number of operators does not fit between the numbers
+    OperatorCount { expected: usize, found: usize },
+    /// An operator symbol that is not in the operator set
+    UnsupportedOperator(String),
+    /// The value overflowed when applying the operator at this index
+    Overflow { operator: usize },
+    /// The sum of the targets does not fit in the value type
+    TotalOverflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::NoNumbers => write!(f, "there are no numbers to combine"),
+            EvalError::OperatorCount { expected, found } => {
+                write!(f, "expected {} operators, found {}", expected, found)
+            }
+            EvalError::UnsupportedOperator(symbol) => write!(f, "unsupported operator {:?}", symbol),
+            EvalError::Overflow { operator } => write!(f, "overflow when applying operator {}", operator + 1),
+            EvalError::TotalOverflow => write!(f, "the total calibration result overflowed"),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+/// Evaluates `numbers` left to right, with the operators named by `symbols`
+/// placed between them.
+pub fn evaluate<V: Value>(numbers: &[V], symbols: &[&str], operators: &OperatorSet<V>) -> Result<V, EvalError> {
+    let Some((&first, rest)) = numbers.split_first() else {
+        return Err(EvalError::NoNumbers);
+    };
+    if symbols.len() != rest.len() {
+        return Err(EvalError::OperatorCount { expected: rest.len(), found: symbols.len() });
+    }
+
+    let mut result = first;
+    for (index, (&symbol, &number)) in symbols.iter().zip(rest).enumerate() {
+        let operator = operators
+            .get(symbol)
+            .ok_or_else(|| EvalError::UnsupportedOperator(symbol.to_string()))?;
+        result = operator
+            .apply(result, number)
+            .ok_or(EvalError::Overflow { operator: index })?;
+    }
+
+    Ok(result)
+}
+
+/// Checks if operators from `operators` can be placed between `numbers` so
+/// that, evaluated left to right, they give `target`.
+///
+/// The search starts from the last number: an operator can only be the last
+/// one if it can be inverted, e.g. `+` needs `target >= last` and `*` needs
+/// `target` to be divisible by `last`. Undoing it gives the target for the
+/// numbers before, so most branches are cut after a few checks instead of
+/// evaluating every operator combination. Inverses are checked too, so a
+/// branch whose values would not fit in `V` is simply never taken.
+///
+/// A zero cannot be undone that way, since e.g. `x * 0 = 0` for every `x`,
+/// so the numbers up to the last zero are evaluated forwards instead.
+pub fn is_solvable<V: Value>(target: V, numbers: &[V], operators: &OperatorSet<V>) -> bool {
+    match numbers {
+        [] => false,
+        [first] => *first == target,
+        [.., last] if *last == V::ZERO => find_forwards(numbers, operators, &mut |value, _| value == target),
+        [rest @ .., last] => operators
+            .iter()
+            .filter_map(|operator| operator.invert(target, *last))
+            .any(|previous| is_solvable(previous, rest, operators)),
+    }
+}
+
+/// Evaluates `numbers` left to right with every combination of `operators`,
+/// skipping those that overflow, and calls `visit` with each value and the
+/// operator symbols used; stops as soon as `visit` returns `true`.
+///
+/// Returns whether `visit` returned `true`.
+pub(crate) fn find_forwards<'a, V: Value>(
+    numbers: &[V],
+    operators: &'a OperatorSet<V>,
+    visit: &mut dyn FnMut(V, &[&'a str]) -> bool,
+) -> bool {
+    fn walk<'a, V: Value>(
+        value: V,
+        numbers: &[V],
+        operators: &'a OperatorSet<V>,
+        symbols: &mut Vec<&'a str>,
+        visit: &mut dyn FnMut(V, &[&'a str]) -> bool,
+    ) -> bool {
+        let Some((&number, rest)) = numbers.split_first() else {
+            return visit(value, symbols);
+        };
+        operators.iter().any(|operator| {
+            let Some(next) = operator.apply(value, number) else {
+                return false;
+            };
+            symbols.push(operator.symbol());
+            let found = walk(next, rest, operators, symbols, visit);
+            symbols.pop();
+            found
+        })
+    }
+
+    match numbers.split_first() {
+        Some((&first, rest)) => walk(first, rest, operators, &mut Vec::new(), visit),
+        None => false,
+    }
+}
+
+/// Sums the targets of the equations that can be made true with `operators`
+pub fn total_calibration<V: Value>(equations: &[Equation<V>], operators: &OperatorSet<V>) -> Result<V, EvalError> {
+    equations
+        .iter()
+        .filter(|equation| is_solvable(equation.target, &equation.numbers, operators))
+        .try_fold(V::ZERO, |total, equation| total.checked_add(equation.target))
+        .ok_or(EvalError::TotalOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::solutions;
+    use crate::{Builtin, Operator};
+
+    #[test]
+    fn expressions_are_evaluated_left_to_right() {
+        let operators = OperatorSet::<u64>::part2();
+        assert_eq!(evaluate(&[81, 40, 27], &["+", "*"], &operators), Ok(3267));
+        assert_eq!(evaluate(&[15, 6], &["||"], &operators), Ok(156));
+        assert_eq!(evaluate(&[7], &[], &operators), Ok(7));
+    }
+
+    #[test]
+    fn expressions_that_cannot_be_evaluated_are_errors() {
+        let operators = OperatorSet::<u64>::part1();
+        assert_eq!(evaluate(&[], &[], &operators), Err(EvalError::NoNumbers));
+        assert_eq!(evaluate(&[1, 2, 3], &["+"], &operators), Err(EvalError::OperatorCount { expected: 2, found: 1 }));
+        assert_eq!(evaluate(&[1, 2], &["||"], &operators), Err(EvalError::UnsupportedOperator("||".to_string())));
+
+        let error = evaluate(&[u64::MAX - 1, 1, 2], &["+", "*"], &operators).unwrap_err();
+        assert_eq!(error, EvalError::Overflow { operator: 1 });
+        assert_eq!(error.to_string(), "overflow when applying operator 2");
+        assert_eq!(
+            evaluate(&[u64::MAX / 10 + 1, 1], &["||"], &OperatorSet::part2()),
+            Err(EvalError::Overflow { operator: 0 })
+        );
+    }
+
+    #[test]
+    fn overflowing_branches_are_never_taken() {
+        let operators = OperatorSet::<u64>::part2();
+        assert!(is_solvable(u64::MAX, &[u64::MAX, 1], &operators));
+        assert!(!is_solvable(2, &[u64::MAX, 2], &operators));
+        assert!(!is_solvable(u64::MAX, &[u64::MAX, 2], &operators));
+    }
+
+    #[test]
+    fn totals_that_overflow_are_errors() {
+        let equations = vec![Equation { target: u64::MAX, numbers: vec![u64::MAX] }; 2];
+        let operators = OperatorSet::part1();
+        assert_eq!(total_calibration(&equations[..1], &operators), Ok(u64::MAX));
+        assert_eq!(total_calibration(&equations, &operators), Err(EvalError::TotalOverflow));
+    }
+
+    #[test]
+    fn u128_values_go_past_u64() {
+        let operators = OperatorSet::<u128>::part2();
+        let above_u64 = u64::MAX as u128 + 1;
+        assert!(is_solvable(above_u64, &[1 << 32, 1 << 32], &operators));
+        assert!(is_solvable(123_456_789_012_345_678_901, &[12_345_678_901_234_567_890, 1], &operators));
+        assert_eq!(evaluate(&[u128::MAX, 1], &["+"], &operators), Err(EvalError::Overflow { operator: 0 }));
+
+        // `u128::MAX` has as many digits as a u128 can hold, so it can only follow a zero
+        assert_eq!(Operator::<u128>::invert(&Builtin::Concat, u128::MAX, u128::MAX), Some(0));
+        assert!(is_solvable(u128::MAX, &[0, u128::MAX], &operators));
+        assert_eq!(u128::MAX.to_answer(), aoc_common::Answer::Text(u128::MAX.to_string()));
+        assert_eq!(above_u64.to_answer(), aoc_common::Answer::Number(above_u64 as i128));
+    }
+
+    #[test]
+    fn zero_operands_are_evaluated_forwards() {
+        let operators = OperatorSet::<u64>::part2();
+        assert!(is_solvable(0, &[5, 0], &operators)); // 5 * 0
+        assert!(is_solvable(12, &[3, 0, 4], &operators)); // 3 + 0 * 4
+        assert!(is_solvable(30, &[3, 0], &operators)); // 3 || 0
+        assert!(is_solvable(5, &[0, 5], &operators)); // 0 + 5
+        assert!(!is_solvable(1, &[0, 0], &operators));
+        assert!(!is_solvable(12, &[3, 0, 4], &OperatorSet::<u64>::new().with(Builtin::Multiply)));
+    }
+
+    #[test]
+    fn zero_operands_keep_every_solution() {
+        let operators = OperatorSet::<u64>::part1();
+        assert_eq!(solutions(0, &[7, 0, 0], &operators), [vec!["+", "*"], vec!["*", "+"], vec!["*", "*"]]);
+        assert_eq!(solutions(12, &[3, 0, 4], &operators), [vec!["+", "*"]]);
+    }
+}
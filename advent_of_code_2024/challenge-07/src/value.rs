@@ -0,0 +1,73 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use aoc_common::solution::Answer;
+use serde::Serialize;
+
+/// An unsigned integer type the equations can be solved with.
+///
+/// Every operation is checked: `None` means the result does not fit in the
+/// type, which the solver treats as "this operator cannot be used here".
+pub trait Value: Copy + Eq + Ord + Debug + Display + FromStr + Serialize + Send + Sync + 'static {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Divides `self` by `other` if it is an exact multiple of it
+    fn checked_div_exact(self, other: Self) -> Option<Self>;
+
+    /// The power of ten that shifts a number left by as many digits as `self` has
+    fn checked_digit_shift(self) -> Option<Self>;
+
+    /// Splits `self` into the number formed by its last `shift` digits and the
+    /// rest, where `shift` is a power of ten
+    fn split_digits(self, shift: Self) -> (Self, Self);
+
+    /// Converts a total to an answer, falling back to text if it does not fit in a number
+    fn to_answer(self) -> Answer;
+}
+
+macro_rules! impl_value {
+    ($($t:ty),*) => {
+        $(
+            impl Value for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_div_exact(self, other: Self) -> Option<Self> {
+                    (other != 0 && self.is_multiple_of(other)).then(|| self / other)
+                }
+
+                fn checked_digit_shift(self) -> Option<Self> {
+                    <$t>::checked_pow(10, self.checked_ilog10().unwrap_or(0) + 1)
+                }
+
+                fn split_digits(self, shift: Self) -> (Self, Self) {
+                    (self / shift, self % shift)
+                }
+
+                fn to_answer(self) -> Answer {
+                    match i128::try_from(self) {
+                        Ok(number) => Answer::Number(number),
+                        Err(_) => Answer::Text(self.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_value!(u64, u128);
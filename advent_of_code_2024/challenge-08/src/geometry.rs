@@ -0,0 +1,221 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::Position;
+
+/// Greatest common divisor of two integers, never negative
+pub fn gcd(mut a: isize, mut b: isize) -> isize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Solves `a * x + b * y = g` with the extended Euclidean algorithm, where `g`
+/// is the greatest common divisor of `a` and `b` up to its sign; returns `(g, x, y)`
+fn extended_gcd(a: isize, b: isize) -> (isize, isize, isize) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    (old_r, old_x, old_y)
+}
+
+/// Division rounding towards negative infinity, for a divisor of either sign
+fn floor_div(a: isize, b: isize) -> isize {
+    let quotient = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+/// Division rounding towards positive infinity, for a divisor of either sign
+fn ceil_div(a: isize, b: isize) -> isize {
+    -floor_div(-a, b)
+}
+
+/// The values of `t` for which `origin + direction * t` lies on a `width` x `height` grid,
+/// or `None` if there are none.
+///
+/// `direction` must not be zero.
+pub fn steps_in_bounds(origin: Position, direction: Position, width: usize, height: usize) -> Option<RangeInclusive<isize>> {
+    debug_assert!(direction != Position::default(), "a zero direction never leaves its origin");
+
+    let mut low = isize::MIN;
+    let mut high = isize::MAX;
+    for (start, step, size) in [(origin.x, direction.x, width as isize), (origin.y, direction.y, height as isize)] {
+        if step == 0 {
+            // The coordinate never changes, so it is either always or never in bounds
+            if !(0..size).contains(&start) {
+                return None;
+            }
+            continue;
+        }
+
+        // 0 <= start + t * step <= size - 1, solved for t
+        let (from, to) = (-start, size - 1 - start);
+        let (first, last) = if step > 0 {
+            (ceil_div(from, step), floor_div(to, step))
+        } else {
+            (ceil_div(to, step), floor_div(from, step))
+        };
+        low = low.max(first);
+        high = high.min(last);
+    }
+
+    (low <= high).then_some(low..=high)
+}
+
+/// A line through grid points, in a canonical form so the same line is always equal to itself.
+///
+/// The line is stored as the equation `dy * x - dx * y = offset`, where the
+/// direction `(dx, dy)` is reduced to its smallest grid step and points right,
+/// or down if the line is vertical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line {
+    direction: Position, // Smallest grid step along the line
+    offset: isize,       // Right-hand side of the line equation
+}
+
+impl Line {
+    /// The line through two distinct grid points
+    pub fn through(a: Position, b: Position) -> Self {
+        assert!(a != b, "a line needs two distinct points, got {:?} twice", a);
+
+        let delta = b - a;
+        let divisor = gcd(delta.x, delta.y);
+        let mut direction = Position::new(delta.x / divisor, delta.y / divisor);
+        if direction.x < 0 || (direction.x == 0 && direction.y < 0) {
+            direction = -direction;
+        }
+
+        Line { direction, offset: direction.y * a.x - direction.x * a.y }
+    }
+
+    /// The smallest grid step along the line, pointing right, or down if the line is vertical
+    pub fn direction(&self) -> Position {
+        self.direction
+    }
+
+    /// Whether the point lies on the line
+    pub fn contains(&self, point: Position) -> bool {
+        self.direction.y * point.x - self.direction.x * point.y == self.offset
+    }
+
+    /// A grid point on the line, found by solving its equation
+    fn anchor(&self) -> Position {
+        // The direction is reduced, so the greatest common divisor is 1 or -1, its own inverse
+        let (divisor, x, y) = extended_gcd(self.direction.y, -self.direction.x);
+        Position::new(x * self.offset * divisor, y * self.offset * divisor)
+    }
+
+    /// Every grid point of the line on a `width` x `height` grid, in order along the line.
+    ///
+    /// The points are computed directly from where the line enters and leaves the grid.
+    pub fn lattice_points(&self, width: usize, height: usize) -> impl Iterator<Item = Position> {
+        let anchor = self.anchor();
+        let direction = self.direction;
+        steps_in_bounds(anchor, direction, width, height)
+            .into_iter()
+            .flatten()
+            .map(move |t| anchor + direction * t)
+    }
+}
+
+/// Every unordered pair of distinct elements of the slice, in order, without allocating
+pub fn pairs<T: Copy>(items: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
+    items
+        .iter()
+        .enumerate()
+        .flat_map(move |(index, &first)| items[index + 1..].iter().map(move |&second| (first, second)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisions_round_the_right_way() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(gcd(7, 0), 7);
+        for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (6, 3), (-6, 3), (0, 4)] {
+            let exact = a as f64 / b as f64;
+            assert_eq!(floor_div(a, b), exact.floor() as isize, "{} / {}", a, b);
+            assert_eq!(ceil_div(a, b), exact.ceil() as isize, "{} / {}", a, b);
+        }
+    }
+
+    #[test]
+    fn extended_gcd_solves_bezout() {
+        for (a, b) in [(3, 5), (-3, 5), (0, 1), (1, 0), (-2, -7), (12, 18)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g.abs(), gcd(a, b));
+        }
+    }
+
+    #[test]
+    fn steps_in_bounds_match_a_walk_over_the_grid() {
+        let (width, height) = (7, 4);
+        for direction in [(1, 0), (0, -1), (2, 1), (-1, 3), (-3, -2)] {
+            let direction = Position::new(direction.0, direction.1);
+            for origin in [(0, 0), (3, 2), (6, 3), (-2, 1), (9, -4)] {
+                let origin = Position::new(origin.0, origin.1);
+                let on_grid = |t: isize| {
+                    let point = origin + direction * t;
+                    (0..width as isize).contains(&point.x) && (0..height as isize).contains(&point.y)
+                };
+
+                let expected: Vec<isize> = (-20..=20).filter(|&t| on_grid(t)).collect();
+                let found: Vec<isize> = steps_in_bounds(origin, direction, width, height).into_iter().flatten().collect();
+                assert_eq!(found, expected, "{:?} + t * {:?}", origin, direction);
+            }
+        }
+    }
+
+    #[test]
+    fn lines_through_any_two_of_their_points_are_equal() {
+        let (a, b, c) = (Position::new(1, 5), Position::new(3, 2), Position::new(7, -4));
+        let line = Line::through(a, b);
+        assert_eq!(line, Line::through(b, a));
+        assert_eq!(line, Line::through(c, a));
+        assert_eq!(line.direction(), Position::new(2, -3));
+        assert!(line.contains(c) && !line.contains(Position::new(2, 3)));
+
+        assert_eq!(Line::through(Position::new(0, 4), Position::new(0, 1)).direction(), Position::new(0, 1));
+        assert_eq!(Line::through(Position::new(6, 0), Position::new(0, 3)).direction(), Position::new(2, -1));
+        assert!(line.contains(line.anchor()));
+    }
+
+    #[test]
+    fn lattice_points_are_every_grid_point_of_the_line() {
+        let (width, height) = (12, 9);
+        let points = [(0, 0), (4, 6), (11, 1), (3, 3), (5, 0), (5, 8), (0, 7)].map(|(x, y)| Position::new(x, y));
+        for (a, b) in pairs(&points) {
+            let line = Line::through(a, b);
+            let expected: Vec<Position> = (0..height as isize)
+                .flat_map(|y| (0..width as isize).map(move |x| Position::new(x, y)))
+                .filter(|&point| line.contains(point))
+                .collect();
+
+            let mut found: Vec<Position> = line.lattice_points(width, height).collect();
+            assert!(found.windows(2).all(|pair| pair[1] - pair[0] == line.direction()), "in order along the line");
+            found.sort_unstable_by_key(|point| (point.y, point.x));
+            assert_eq!(found, expected, "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn pairs_are_unordered_and_distinct() {
+        assert_eq!(pairs(&[1, 2, 3]).collect::<Vec<_>>(), [(1, 2), (1, 3), (2, 3)]);
+        assert_eq!(pairs(&[1]).count(), 0);
+        assert_eq!(pairs::<u8>(&[]).count(), 0);
+    }
+}
@@ -0,0 +1,33 @@
+pub mod geometry;
+pub mod resonance;
+
+use aoc_common::solution::{Answer, Error, Solution};
+use aoc_common::Grid;
+
+pub use geometry::{pairs, Line};
+pub use resonance::{AntennaMap, Antinodes, FrequencyAntinodes, Harmonics, Resonance, Step};
+
+/// Solver for Day 8: Resonant Collinearity
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    // The antennas of the map, grouped by frequency
+    type Input = AntennaMap;
+
+    fn parse(input: &str) -> Result<AntennaMap, Error> {
+        Ok(AntennaMap::new(&Grid::parse(input, |c| c)?))
+    }
+
+    fn part1(map: &AntennaMap) -> Result<Answer, Error> {
+        // Count the antinodes of the first harmonic only
+        Ok(Resonance::part1().antinodes(map).unique().into())
+    }
+
+    fn part2(map: &AntennaMap) -> Result<Answer, Error> {
+        // Count every grid position in line with two antennas of the same frequency
+        Ok(Resonance::part2().antinodes(map).unique().into())
+    }
+}
@@ -1,171 +1,69 @@
-use std::collections::{HashMap, HashSet};
-use std::fs;
-
-/// Type alias for positions on the grid (row, col)
-/// It is used to represent the coordinates of antennas and antinodes
-type Position = (isize, isize);
-
-/// Returns a new position representing the vector difference of the input positions.
-fn subtract_positions(a: Position, b: Position) -> Position {
-    (a.0 - b.0, a.1 - b.1)
-}
-
-/// Returns a new position by adding the coordinates of the two input positions.
-fn add_positions(a: Position, b: Position) -> Position {
-    (a.0 + b.0, a.1 + b.1)
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_common::input::DEFAULT_INPUT;
+use aoc_common::solution::{run_main, run_with, Error};
+use challenge_08::{AntennaMap, Day08, Harmonics, Resonance, Step};
+use clap::Parser;
+
+/// Solves day 8, or breaks the antinodes down by frequency.
+#[derive(Parser)]
+#[command(name = "challenge-08")]
+struct Cli {
+    /// Puzzle input file
+    #[arg(default_value = DEFAULT_INPUT)]
+    input: PathBuf,
+
+    /// List the antinodes of every frequency instead of only counting them
+    #[arg(long)]
+    breakdown: bool,
+
+    /// Harmonics producing antinodes, as `K`, `FIRST..` or `FIRST..=LAST`, instead of those of parts 1 and 2
+    #[arg(long, value_name = "RANGE", requires = "breakdown")]
+    harmonics: Option<Harmonics>,
+
+    /// Space harmonics by the smallest grid step along each line instead of the antenna separation
+    #[arg(long, requires = "harmonics")]
+    lattice: bool,
 }
 
-/// Rotates a direction by 180 degrees (inverts the vector)
-fn rotate180(direction: Position) -> Position {
-    (-direction.0, -direction.1)
-}
-
-/// Calculates the greatest common divisor (GCD) of two integers using the Euclidean algorithm
-fn gcd(a: isize, b: isize) -> isize {
-    if b == 0 {
-        a.abs() // Return the absolute value for clarity
-    } else {
-        gcd(b, a % b)
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if !cli.breakdown {
+        return run_main::<Day08>(&cli.input);
     }
-}
 
-/// Iteratively follows a direction from a starting position until no further valid positions exist in the map
-/// Returns a vector of all valid positions encountered along the path
-fn iter(position: Position, direction: Position, map: &HashMap<Position, char>) -> Vec<Position> {
-    let mut result = Vec::new();
-    let mut current = position;
-    while map.contains_key(&current) {
-        result.push(current); // Add the current position to the result
-        current = add_positions(current, direction); // Move in the specified direction.
-    }
-    result
+    run_with::<Day08>(&cli.input, |map| breakdown(&cli, map))
 }
 
-/// Parses the input grid into a HashMap of positions and their corresponding frequencies
-/// Input: A vector of strings, where each character represents either an antenna (frequency) or an empty space
-/// Output: A HashMap mapping grid positions to their respective characters (frequencies)
-fn parse_input(input: &[String]) -> HashMap<Position, char> {
-    let mut grid = HashMap::new();
-    for (row, line) in input.iter().enumerate() {
-        for (col, ch) in line.chars().enumerate() {
-            grid.insert((row as isize, col as isize), ch); // Convert grid indices to isize for flexibility
-        }
-    }
-    grid
-}
+fn breakdown(cli: &Cli, map: &AntennaMap) -> Result<(), Error> {
 
-/// Generate all unique pairs of items from a list
-/// Input: A list of items
-/// Output: A vector of tuples, each containing a unique pair of items from the list
-fn generate_pairs<T: Clone>(list: &[T]) -> Vec<(T, T)> {
-    if list.len() < 2 {
-        vec![] // Return an empty vector if there are fewer than two items.
-    } else {
-        let head = &list[0];
-        let tail = &list[1..];
-        let mut result = tail
-            .iter()
-            .map(|item| (head.clone(), item.clone()))
-            .collect::<Vec<_>>(); // Create pairs with the first item and all remaining items.
-        result.extend(generate_pairs(tail)); // Recursively generate pairs from the tail.
-        result
-    }
-}
-
-/// Finds antinodes based on the positions of the two antennas
-/// Logic: For each pair of antennas, calculate potential antinode positions based on their relative positions
-/// Filters antinodes to include only valid grid positions
-fn find_antinodes_part1(
-    grid: &HashMap<Position, char>,
-    antenna_a: Position,
-    antenna_b: Position,
-) -> Vec<Position> {
-    vec![
-        add_positions(antenna_a, subtract_positions(antenna_a, antenna_b)), // First antinode
-        add_positions(antenna_b, subtract_positions(antenna_b, antenna_a)), // Second antinode
-    ]
-    .into_iter()
-    .filter(|pos| grid.contains_key(pos)) // Keep only positions that exist in the grid
-    .collect()
-}
-
-/// Finds antinodes for Part 2.
-/// Logic: Extends a line in both directions from one antenna to another, capturing all valid antinodes along the way.
-fn find_antinodes_part2(
-    map: &HashMap<Position, char>,
-    antenna_a: Position,
-    antenna_b: Position,
-) -> Vec<Position> {
-    let (delta_row, delta_col) = subtract_positions(antenna_a, antenna_b);
-    let gcd_val = gcd(delta_row, delta_col); // Normalize the direction vector.
-    let direction = (delta_row / gcd_val, delta_col / gcd_val);
-    let mut antinodes = Vec::new();
-    antinodes.extend(iter(antenna_a, direction, map)); // Extend in the normalized direction.
-    antinodes.extend(iter(antenna_a, rotate180(direction), map)); // Extend in the opposite direction.
-    antinodes
-}
-
-/// Solves the problem using the given antinode finding function
-/// Input:
-/// - The parsed input grid as a vector of strings
-/// - A function to determine the antinodes for a pair of antennas
-/// Process:
-/// - Groups antennas by frequency
-/// - For each frequency group, generates all pairs of antennas
-/// - Computes antinodes for each pair and adds them to a unique set
-/// Output: The total count of unique antinode positions
-fn solve(
-    input: &[String],
-    find_antinodes: fn(&HashMap<Position, char>, Position, Position) -> Vec<Position>,
-) -> usize {
-    // Parse the input into a grid of positions and their respective characters (frequencies)
-    let grid = parse_input(input);
-
-    // Group all antenna positions by their frequency
-    let grouped_by_frequency = grid
-        .iter()
-        .filter(|&(_, &freq)| freq != '.') // Ignore empty cells
-        .fold(HashMap::new(), |mut acc, (&position, &freq)| {
-            acc.entry(freq).or_insert_with(Vec::new).push(position);
-            acc
-        });
-
-    // Use a HashSet to keep track of unique antinode positions
-    let mut unique_antinode_positions = HashSet::new();
-
-    // Process each frequency group.
-    for antennas in grouped_by_frequency.values() {
-        let antenna_pairs = generate_pairs(antennas); // Generate all unique pairs of antennas in the group
-        for (antenna_a, antenna_b) in antenna_pairs {
-            unique_antinode_positions.extend(find_antinodes(&grid, antenna_a, antenna_b)); // Find and collect antinodes
+    let resonances = match cli.harmonics {
+        Some(harmonics) => {
+            let step = if cli.lattice { Step::Lattice } else { Step::Separation };
+            vec![(format!("harmonics {}", harmonics), Resonance::new(harmonics).with_step(step))]
+        }
+        None => vec![("part 1".to_string(), Resonance::part1()), ("part 2".to_string(), Resonance::part2())],
+    };
+
+    for (label, resonance) in resonances {
+        let antinodes = resonance.antinodes(map);
+        println!("{}: {} distinct antinodes", label, antinodes.unique());
+        for frequency in antinodes.by_frequency() {
+            let positions: Vec<String> = frequency
+                .antinodes
+                .iter()
+                .map(|position| format!("({},{})", position.x, position.y))
+                .collect();
+            println!(
+                "  {}: {} antennas, {} antinodes {}",
+                frequency.frequency,
+                frequency.antennas,
+                frequency.antinodes.len(),
+                positions.join(" ")
+            );
         }
     }
 
-    unique_antinode_positions.len()
+    Ok(())
 }
-
-/// Reads the puzzle input file and returns the grid as a vector of strings
-/// Input: The file path to the puzzle input file
-/// Output: A vector of strings, each representing a row of the grid
-fn read_input(file_path: &str) -> Vec<String> {
-    fs::read_to_string(file_path) // Read the file contents as a single string
-        .expect("Failed to read input file") // Panic if the file cannot be read
-        .lines() // Split the string into lines
-        .map(String::from) // Convert each line to a String
-        .collect() // Collect the lines into a vector
-}
-
-fn main() {
-    // Read the input file containing the grid representation
-    let input = read_input("puzzle_input.txt");
-
-    // Solve the problem for Part 1 using the specific antinode calculation function
-    let unique_locations = solve(&input, find_antinodes_part1);
-
-    println!("Unique Locations: {}", unique_locations);
-
-    // Solve the problem for Part2 using the specific antinode calculation function
-    let unique_locations_part_2 = solve(&input, find_antinodes_part2);
-
-    println!("Unique Locations Part 2: {}", unique_locations_part_2);
-}
\ No newline at end of file
@@ -0,0 +1,428 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+use std::str::FromStr;
+
+use aoc_common::{Grid, Position};
+
+use crate::geometry::{gcd, pairs, steps_in_bounds, Line};
+
+/// The antennas of a map, grouped by frequency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AntennaMap {
+    width: usize,                            // Number of columns of the map
+    height: usize,                           // Number of rows of the map
+    frequencies: Vec<(char, Vec<Position>)>, // Antennas of every frequency, sorted by frequency
+}
+
+impl AntennaMap {
+    /// Groups the antennas of the map by frequency; `.` marks an empty cell
+    pub fn new(grid: &Grid<char>) -> Self {
+        let mut frequencies: Vec<(char, Vec<Position>)> = Vec::new();
+        for (position, &frequency) in grid.iter().filter(|&(_, &cell)| cell != '.') {
+            match frequencies.iter_mut().find(|(known, _)| *known == frequency) {
+                Some((_, antennas)) => antennas.push(position),
+                None => frequencies.push((frequency, vec![position])),
+            }
+        }
+        frequencies.sort_unstable_by_key(|&(frequency, _)| frequency);
+
+        AntennaMap { width: grid.width(), height: grid.height(), frequencies }
+    }
+
+    /// Number of columns of the map
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows of the map
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the position lies on the map
+    pub fn in_bounds(&self, position: Position) -> bool {
+        (0..self.width as isize).contains(&position.x) && (0..self.height as isize).contains(&position.y)
+    }
+
+    /// Every frequency with the positions of its antennas, sorted by frequency
+    pub fn frequencies(&self) -> impl Iterator<Item = (char, &[Position])> {
+        self.frequencies.iter().map(|(frequency, antennas)| (*frequency, antennas.as_slice()))
+    }
+}
+
+/// Range of harmonics `k` at which a pair of antennas produces antinodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Harmonics {
+    first: usize,        // Smallest harmonic
+    last: Option<usize>, // Largest harmonic, or `None` to go on until the edge of the map
+}
+
+impl Harmonics {
+    /// Harmonics in `range`, e.g. `1..=1`, `0..` or `2..=5`
+    pub fn new<R: RangeBounds<usize>>(range: R) -> Self {
+        let first = match range.start_bound() {
+            Bound::Included(&first) => first,
+            Bound::Excluded(&first) => first.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let last = match range.end_bound() {
+            Bound::Included(&last) => Some(last),
+            Bound::Excluded(&end) => match end.checked_sub(1) {
+                Some(last) => Some(last),
+                None => return Harmonics { first: 1, last: Some(0) }, // Nothing is below 0
+            },
+            Bound::Unbounded => None,
+        };
+        Harmonics { first, last }
+    }
+
+    /// Smallest harmonic
+    pub fn first(&self) -> usize {
+        self.first
+    }
+
+    /// Largest harmonic, or `None` if unbounded
+    pub fn last(&self) -> Option<usize> {
+        self.last
+    }
+
+    /// Whether the range holds no harmonic at all
+    pub fn is_empty(&self) -> bool {
+        self.last.is_some_and(|last| last < self.first)
+    }
+}
+
+impl fmt::Display for Harmonics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.last {
+            Some(last) if last == self.first => write!(f, "{}", last),
+            Some(last) => write!(f, "{}..={}", self.first, last),
+            None => write!(f, "{}..", self.first),
+        }
+    }
+}
+
+/// Error returned when a range of harmonics cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HarmonicsError(String);
+
+impl fmt::Display for HarmonicsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid harmonics {:?}: expected `K`, `FIRST..` or `FIRST..=LAST`", self.0)
+    }
+}
+
+impl Error for HarmonicsError {}
+
+impl FromStr for Harmonics {
+    type Err = HarmonicsError;
+
+    /// Parses `K`, `FIRST..` or `FIRST..=LAST`
+    fn from_str(text: &str) -> Result<Self, HarmonicsError> {
+        let invalid = || HarmonicsError(text.to_string());
+        let number = |number: &str| number.trim().parse::<usize>().map_err(|_| invalid());
+
+        match text.split_once("..") {
+            None => number(text).map(|k| Harmonics::new(k..=k)),
+            Some((first, "")) => number(first).map(|first| Harmonics::new(first..)),
+            Some((first, last)) => {
+                let last = last.strip_prefix('=').ok_or_else(invalid)?;
+                Ok(Harmonics::new(number(first)?..=number(last)?))
+            }
+        }
+    }
+}
+
+/// Distance between two consecutive harmonics of a pair of antennas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The separation of the two antennas
+    Separation,
+    /// The smallest move along the line through the two antennas that lands on the grid,
+    /// so that every grid position in line with them can be reached; harmonics are then
+    /// counted both ways from each antenna, so positions between the antennas are reached too
+    Lattice,
+}
+
+/// Antinodes of one frequency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrequencyAntinodes {
+    pub frequency: char,          // The frequency of the antennas
+    pub antennas: usize,          // How many antennas share it
+    pub antinodes: Vec<Position>, // Distinct antinodes on the map, in reading order
+}
+
+/// Antinodes of every frequency of a map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Antinodes {
+    by_frequency: Vec<FrequencyAntinodes>, // Antinodes of every frequency, sorted by frequency
+    unique: usize,                         // Distinct antinodes of any frequency
+}
+
+impl Antinodes {
+    /// Antinodes of every frequency, sorted by frequency
+    pub fn by_frequency(&self) -> &[FrequencyAntinodes] {
+        &self.by_frequency
+    }
+
+    /// Number of distinct positions that are an antinode of at least one frequency
+    pub fn unique(&self) -> usize {
+        self.unique
+    }
+}
+
+/// How pairs of antennas of the same frequency resonate.
+///
+/// For a pair of antennas `a` and `b`, the `k`-th harmonic produces an
+/// antinode `k` steps beyond `a`, away from `b`, and another `k` steps beyond
+/// `b`, away from `a`. The 0-th harmonic is the antennas themselves.
+///
+/// With `Step::Lattice`, harmonics are counted both ways from each antenna,
+/// so with harmonics `0..` the antinodes are every grid position on the line
+/// through the pair, including those between the antennas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resonance {
+    harmonics: Harmonics, // Harmonics producing antinodes
+    step: Step,           // Distance between consecutive harmonics
+}
+
+impl Resonance {
+    /// Antinodes at the given harmonics, one antenna separation apart
+    pub fn new(harmonics: Harmonics) -> Self {
+        Resonance { harmonics, step: Step::Separation }
+    }
+
+    /// Antinodes at twice the distance from one antenna as from the other
+    pub fn part1() -> Self {
+        Resonance::new(Harmonics::new(1..=1))
+    }
+
+    /// Antinodes at every grid position in line with two antennas
+    pub fn part2() -> Self {
+        Resonance::new(Harmonics::new(0..)).with_step(Step::Lattice)
+    }
+
+    /// Uses a different distance between consecutive harmonics
+    pub fn with_step(mut self, step: Step) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// The harmonics producing antinodes
+    pub fn harmonics(&self) -> Harmonics {
+        self.harmonics
+    }
+
+    /// The harmonics from `first` onwards that are at most `last_on_map`
+    fn up_to(&self, first: usize, last_on_map: usize) -> RangeInclusive<usize> {
+        let last = self.harmonics.last.map_or(last_on_map, |last| last.min(last_on_map));
+        self.harmonics.first.max(first)..=last
+    }
+
+    /// Calls `visit` with the antinodes of the pair that lie on the map: from
+    /// each antenna outwards, and with `Step::Lattice` also towards the other one.
+    ///
+    /// The harmonics that land on the map are computed from where the line
+    /// leaves it, so no step off the map is ever tried.
+    fn for_each_antinode<F>(&self, map: &AntennaMap, antenna_a: Position, antenna_b: Position, mut visit: F)
+    where
+        F: FnMut(Position),
+    {
+        let delta = antenna_a - antenna_b;
+        let step = match self.step {
+            Step::Separation => delta,
+            Step::Lattice => {
+                let divisor = gcd(delta.x, delta.y);
+                Position::new(delta.x / divisor, delta.y / divisor)
+            }
+        };
+
+        for (antenna, step) in [(antenna_a, step), (antenna_b, -step)] {
+            // The antenna is on the map, so the steps on it run from at most 0 to at least 0
+            let Some(steps) = steps_in_bounds(antenna, step, map.width, map.height) else {
+                continue;
+            };
+
+            for k in self.up_to(0, *steps.end() as usize) {
+                visit(antenna + step * k as isize);
+            }
+            if self.step == Step::Lattice {
+                // The 0-th harmonic is the antenna itself, already visited
+                for k in self.up_to(1, steps.start().unsigned_abs()) {
+                    visit(antenna - step * k as isize);
+                }
+            }
+        }
+    }
+
+    /// Whether the harmonics of a pair together cover the whole line through it on the map.
+    ///
+    /// Every grid step moves at least one cell along the map's width or height,
+    /// so no two points of a line on the map are `max(width, height)` steps apart.
+    fn covers_lines(&self, map: &AntennaMap) -> bool {
+        let longest = map.width.max(map.height).saturating_sub(1);
+        self.step == Step::Lattice && self.harmonics.first == 0 && self.harmonics.last.is_none_or(|last| last >= longest)
+    }
+
+    /// Finds the antinodes of every frequency of the map
+    pub fn antinodes(&self, map: &AntennaMap) -> Antinodes {
+        let mut any_frequency = Grid::filled(map.width, map.height, false);
+        let mut this_frequency = Grid::filled(map.width, map.height, false);
+        let mut by_frequency = Vec::new();
+
+        for (frequency, antennas) in map.frequencies() {
+            let mut antinodes = Vec::new();
+            let mut mark = |antinode: Position| {
+                if !std::mem::replace(&mut this_frequency[antinode], true) {
+                    antinodes.push(antinode);
+                }
+            };
+
+            if self.covers_lines(map) {
+                // Collinear antennas share a line, so each line is only intersected with the map once
+                let lines: HashSet<Line> = pairs(antennas).map(|(a, b)| Line::through(a, b)).collect();
+                for line in lines {
+                    line.lattice_points(map.width, map.height).for_each(&mut mark);
+                }
+            } else {
+                for (antenna_a, antenna_b) in pairs(antennas) {
+                    self.for_each_antinode(map, antenna_a, antenna_b, &mut mark);
+                }
+            }
+
+            // Clear the marks of this frequency for the next one
+            for &antinode in &antinodes {
+                this_frequency[antinode] = false;
+                any_frequency[antinode] = true;
+            }
+
+            antinodes.sort_unstable_by_key(|position| (position.y, position.x));
+            by_frequency.push(FrequencyAntinodes { frequency, antennas: antennas.len(), antinodes });
+        }
+
+        let unique = any_frequency.iter().filter(|&(_, &antinode)| antinode).count();
+        Antinodes { by_frequency, unique }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(text: &str) -> AntennaMap {
+        AntennaMap::new(&Grid::parse(text, |c| c).unwrap())
+    }
+
+    /// Every position that is `k` steps from an antenna for some harmonic `k`,
+    /// checked cell by cell: outwards only with `Step::Separation`, both ways with `Step::Lattice`
+    fn brute_force(resonance: &Resonance, map: &AntennaMap) -> usize {
+        let harmonics = resonance.harmonics();
+        let in_range = |k: isize| {
+            let k = k.unsigned_abs();
+            k >= harmonics.first() && harmonics.last().is_none_or(|last| k <= last)
+        };
+
+        let grid = Grid::filled(map.width(), map.height(), ());
+        grid.positions()
+            .filter(|&cell| {
+                map.frequencies().any(|(_, antennas)| {
+                    pairs(antennas).any(|(a, b)| {
+                        let delta = a - b;
+                        let divisor = match resonance.step {
+                            Step::Separation => 1,
+                            Step::Lattice => gcd(delta.x, delta.y),
+                        };
+                        let step = Position::new(delta.x / divisor, delta.y / divisor);
+                        [(a, step), (b, -step)].into_iter().any(|(antenna, step)| {
+                            let offset = cell - antenna;
+                            let k = if step.x != 0 { offset.x / step.x } else { offset.y / step.y };
+                            antenna + step * k == cell && in_range(k) && (k >= 0 || resonance.step == Step::Lattice)
+                        })
+                    })
+                })
+            })
+            .count()
+    }
+
+    const MAPS: [&str; 3] = [
+        include_str!("../examples/example.txt"),
+        include_str!("../examples/collinear.txt"),
+        "a.....\n......\n..a...\n......\n....a.\n......\n",
+    ];
+
+    #[test]
+    fn antinodes_match_a_brute_force_search() {
+        for text in MAPS {
+            let map = map(text);
+            for harmonics in ["0..", "1..", "1", "0..=2", "2..=3", "0..=0", "3..=2"] {
+                for step in [Step::Separation, Step::Lattice] {
+                    let resonance = Resonance::new(harmonics.parse().unwrap()).with_step(step);
+                    let expected = brute_force(&resonance, &map);
+                    assert_eq!(resonance.antinodes(&map).unique(), expected, "{} {:?}\n{}", harmonics, step, text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lattice_antinodes_fill_the_gaps_between_antennas() {
+        let map = map(MAPS[1]);
+        let antinodes = Resonance::part2().antinodes(&map);
+        let a = &antinodes.by_frequency()[0];
+        assert_eq!(a.antinodes, [Position::new(0, 0), Position::new(1, 0), Position::new(2, 0), Position::new(3, 0)]);
+        assert_eq!(antinodes.unique(), 7);
+
+        // Harmonic 1 of the lattice is one grid step from each antenna, both ways
+        let first = Resonance::new(Harmonics::new(1..=1)).with_step(Step::Lattice).antinodes(&map);
+        assert_eq!(first.by_frequency()[0].antinodes, [Position::new(1, 0), Position::new(2, 0)]);
+    }
+
+    #[test]
+    fn unbounded_and_large_bounded_lattice_harmonics_agree() {
+        for text in MAPS {
+            let map = map(text);
+            let unbounded = Resonance::part2().antinodes(&map);
+            let longest = map.width().max(map.height());
+            for last in [longest - 1, longest, 100, usize::MAX] {
+                let bounded = Resonance::new(Harmonics::new(0..=last)).with_step(Step::Lattice);
+                assert_eq!(bounded.antinodes(&map), unbounded, "0..={}", last);
+            }
+        }
+    }
+
+    #[test]
+    fn the_line_fast_path_matches_the_walk_along_each_pair() {
+        for text in MAPS {
+            let map = map(text);
+            let resonance = Resonance::part2();
+            assert!(resonance.covers_lines(&map));
+            for (_, antennas) in map.frequencies() {
+                for (a, b) in pairs(antennas) {
+                    let mut walked = Vec::new();
+                    resonance.for_each_antinode(&map, a, b, |antinode| walked.push(antinode));
+                    walked.sort_unstable();
+                    walked.dedup();
+
+                    let mut line: Vec<Position> = Line::through(a, b).lattice_points(map.width(), map.height()).collect();
+                    line.sort_unstable();
+                    assert_eq!(walked, line);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn harmonics_parse_and_display_alike() {
+        for text in ["3", "0..", "2..=5"] {
+            assert_eq!(text.parse::<Harmonics>().unwrap().to_string(), text);
+        }
+        assert_eq!("4..=4".parse::<Harmonics>().unwrap(), Harmonics::new(4..=4));
+        assert!(Harmonics::new(0..0).is_empty());
+        assert!("3..=2".parse::<Harmonics>().unwrap().is_empty());
+        for text in ["", "..", "1..5", "-1", "a..=b"] {
+            assert!(text.parse::<Harmonics>().is_err(), "{:?}", text);
+        }
+    }
+}
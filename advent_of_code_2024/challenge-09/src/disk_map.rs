@@ -0,0 +1,322 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+use std::ops::Range;
+
+/// How files are moved to the left to fill the free space on the disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactionStrategy {
+    /// Move one block at a time, from the end of the disk to the leftmost free block (part 1)
+    Blocks,
+    /// Move whole files, in decreasing file ID order, to the leftmost free span that can hold them (part 2)
+    WholeFiles,
+}
+
+/// A contiguous run of blocks on the disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    start: usize, // Index of the first block
+    len: usize,   // Number of blocks
+}
+
+/// Error returned when a disk map cannot be read
+#[derive(Debug)]
+pub enum DiskMapError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// A byte that is not a digit was found at the given offset; for a digit
+    /// after trailing whitespace, this is the whitespace that ended the map
+    InvalidDigit { offset: usize, found: char },
+}
+
+impl fmt::Display for DiskMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiskMapError::Io(e) => write!(f, "cannot read disk map: {}", e),
+            DiskMapError::InvalidDigit { offset, found } => {
+                write!(f, "expected a digit at offset {}, found {:?}", offset, found)
+            }
+        }
+    }
+}
+
+impl Error for DiskMapError {}
+
+impl From<io::Error> for DiskMapError {
+    fn from(e: io::Error) -> Self {
+        DiskMapError::Io(e)
+    }
+}
+
+/// The layout of the disk, one entry per block: the ID of the file stored in
+/// it, or `None` if the block is free.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskMap {
+    blocks: Vec<Option<u32>>, // File ID stored in every block
+    files: Vec<Vec<Span>>,    // Fragments of every file, from left to right, indexed by file ID
+}
+
+impl DiskMap {
+    /// Parses the dense disk map format, e.g. `2333133121414131402`.
+    ///
+    /// Digits alternate between the length of a file and the length of the
+    /// free space that follows it. Trailing whitespace is ignored.
+    pub fn parse(dense: &str) -> Result<DiskMap, DiskMapError> {
+        DiskMap::from_reader(dense.as_bytes())
+    }
+
+    /// Streams the dense disk map format from `reader`, without loading the
+    /// whole input into memory first.
+    pub fn from_reader<R: Read>(reader: R) -> Result<DiskMap, DiskMapError> {
+        let mut reader = BufReader::new(reader);
+        let mut disk = DiskMap { blocks: Vec::new(), files: Vec::new() };
+        let mut offset = 0;
+        let mut end: Option<(usize, char)> = None; // First whitespace after the map, and its offset
+
+        loop {
+            let chunk = reader.fill_buf()?;
+            if chunk.is_empty() {
+                break;
+            }
+            let consumed = chunk.len();
+
+            for &byte in chunk {
+                match (byte, end) {
+                    (b'0'..=b'9', None) => disk.push_digit(offset, byte - b'0'),
+                    // The whitespace was not trailing after all, so it is what broke the map
+                    (b'0'..=b'9', Some((offset, found))) => return Err(DiskMapError::InvalidDigit { offset, found }),
+                    (b, _) if b.is_ascii_whitespace() => {
+                        end.get_or_insert((offset, byte as char));
+                    }
+                    _ => return Err(DiskMapError::InvalidDigit { offset, found: byte as char }),
+                }
+                offset += 1;
+            }
+
+            reader.consume(consumed);
+        }
+
+        Ok(disk)
+    }
+
+    /// Appends the file or free span described by the digit at `offset`
+    fn push_digit(&mut self, offset: usize, len: u8) {
+        let len = len as usize;
+
+        // Even offsets describe files, odd offsets the free space after them
+        if offset.is_multiple_of(2) {
+            let file_id = self.files.len() as u32;
+            let fragments = if len > 0 { vec![Span { start: self.blocks.len(), len }] } else { Vec::new() };
+            self.files.push(fragments);
+            self.blocks.extend(std::iter::repeat_n(Some(file_id), len));
+        } else {
+            self.blocks.extend(std::iter::repeat_n(None, len));
+        }
+    }
+
+    /// Number of blocks on the disk
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Checks if the disk has no blocks at all
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// The file ID stored in every block, or `None` for free blocks
+    pub fn blocks(&self) -> &[Option<u32>] {
+        &self.blocks
+    }
+
+    /// The blocks occupied by the file, one range per fragment from left to
+    /// right, or `None` if there is no such file.
+    ///
+    /// Every file starts as a single fragment, or none if it is empty;
+    /// only block compaction splits files.
+    pub fn file_blocks(&self, file_id: u32) -> Option<Vec<Range<usize>>> {
+        let fragments = self.files.get(file_id as usize)?;
+        Some(fragments.iter().map(|span| span.start..span.start + span.len).collect())
+    }
+
+    /// Moves files to the left using the given strategy
+    pub fn compact(&mut self, strategy: CompactionStrategy) {
+        match strategy {
+            CompactionStrategy::Blocks => self.compact_blocks(),
+            CompactionStrategy::WholeFiles => self.compact_whole_files(),
+        }
+    }
+
+    /// Moves the last file block into the first free block until no gaps are left.
+    ///
+    /// Two pointers walk towards each other, so every block is visited once.
+    fn compact_blocks(&mut self) {
+        if self.blocks.is_empty() {
+            return;
+        }
+
+        let mut left = 0;
+        let mut right = self.blocks.len() - 1;
+
+        loop {
+            // Find the first free block from the left
+            while left < right && self.blocks[left].is_some() {
+                left += 1;
+            }
+            // Find the last file block from the right
+            while left < right && self.blocks[right].is_none() {
+                right -= 1;
+            }
+            if left >= right {
+                break;
+            }
+            self.blocks.swap(left, right);
+        }
+
+        self.files = self.fragments();
+    }
+
+    /// Collects the runs of blocks of every file, from left to right
+    fn fragments(&self) -> Vec<Vec<Span>> {
+        let mut files = vec![Vec::new(); self.files.len()];
+        for (index, block) in self.blocks.iter().enumerate() {
+            let Some(file_id) = *block else {
+                continue;
+            };
+            let fragments: &mut Vec<Span> = &mut files[file_id as usize];
+            match fragments.last_mut() {
+                Some(span) if span.start + span.len == index => span.len += 1,
+                _ => fragments.push(Span { start: index, len: 1 }),
+            }
+        }
+        files
+    }
+
+    /// Moves every file once, in decreasing file ID order, to the leftmost free
+    /// span that can hold it.
+    ///
+    /// Free spans are kept in one min-heap (by start) per span length; since
+    /// the dense format describes spans of at most 9 blocks (longer ones only
+    /// appear around empty files), finding the leftmost fitting span only has
+    /// to look at the top of a handful of heaps.
+    fn compact_whole_files(&mut self) {
+        let free_spans = self.free_spans();
+        let longest = free_spans.iter().map(|span| span.len).max().unwrap_or(0);
+
+        let mut free_by_len: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); longest + 1];
+        for span in free_spans {
+            free_by_len[span.len].push(Reverse(span.start));
+        }
+
+        for file_id in (0..self.files.len()).rev() {
+            // Empty files have nothing to move, and split ones cannot move whole
+            let [file] = self.files[file_id][..] else {
+                continue;
+            };
+
+            // Leftmost free span, before the file, that is at least as long as the file
+            let best = (file.len..free_by_len.len())
+                .filter_map(|len| free_by_len[len].peek().map(|&Reverse(start)| (start, len)))
+                .filter(|&(start, _)| start < file.start)
+                .min();
+
+            let Some((free_start, free_len)) = best else {
+                continue;
+            };
+            free_by_len[free_len].pop();
+
+            // Move the file and give the rest of the span back to the pool
+            for offset in 0..file.len {
+                self.blocks[free_start + offset] = Some(file_id as u32);
+                self.blocks[file.start + offset] = None;
+            }
+            if free_len > file.len {
+                free_by_len[free_len - file.len].push(Reverse(free_start + file.len));
+            }
+            self.files[file_id] = vec![Span { start: free_start, len: file.len }];
+        }
+    }
+
+    /// Collects the runs of consecutive free blocks, from left to right
+    fn free_spans(&self) -> Vec<Span> {
+        let mut spans = Vec::new();
+        let mut start = 0;
+
+        while start < self.blocks.len() {
+            let mut end = start;
+            while end < self.blocks.len() && self.blocks[end].is_none() {
+                end += 1;
+            }
+            if end > start {
+                spans.push(Span { start, len: end - start });
+                start = end;
+            } else {
+                start += 1;
+            }
+        }
+
+        spans
+    }
+
+    /// Calculate checksum of the disk: the sum of every block position
+    /// multiplied by the file ID stored in it, or `None` if it overflows a `u64`
+    pub fn checksum(&self) -> Option<u64> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, block)| block.map(|file_id| (index as u64).checked_mul(file_id as u64)))
+            .try_fold(0u64, |sum, product| sum.checked_add(product?))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)] // Files of a single fragment
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_after_whitespace_reports_the_whitespace() {
+        match DiskMap::parse("12\n345") {
+            Err(DiskMapError::InvalidDigit { offset, found }) => assert_eq!((offset, found), (2, '\n')),
+            other => panic!("expected an invalid digit, got {:?}", other),
+        }
+        assert!(DiskMap::parse("12345 \n\n").is_ok());
+    }
+
+    #[test]
+    fn block_compaction_keeps_file_fragments() {
+        let mut disk = DiskMap::parse("12345").unwrap();
+        disk.compact(CompactionStrategy::Blocks);
+
+        // 022111222......
+        assert_eq!(disk.file_blocks(0), Some(vec![0..1]));
+        assert_eq!(disk.file_blocks(1), Some(vec![3..6]));
+        assert_eq!(disk.file_blocks(2), Some(vec![1..3, 6..9]));
+        assert_eq!(disk.file_blocks(3), None);
+
+        // Split files cannot move whole, and nothing else has room to move
+        let compacted = disk.clone();
+        disk.compact(CompactionStrategy::WholeFiles);
+        assert_eq!(disk, compacted);
+    }
+
+    #[test]
+    fn whole_file_compaction_moves_file_spans() {
+        let mut disk = DiskMap::parse("1313").unwrap();
+        disk.compact(CompactionStrategy::WholeFiles);
+        assert_eq!(disk.file_blocks(1), Some(vec![1..2]));
+        assert_eq!(disk.checksum(), Some(1));
+    }
+
+    #[test]
+    fn checksum_overflow_is_detected() {
+        let disk = DiskMap { blocks: vec![None, Some(u32::MAX), Some(u32::MAX)], files: Vec::new() };
+        assert_eq!(disk.checksum(), Some(3 * u32::MAX as u64));
+
+        // The positions alone add up to about 5 * 10^9
+        let disk = DiskMap { blocks: vec![Some(u32::MAX); 100_000], files: Vec::new() };
+        assert_eq!(disk.checksum(), None);
+    }
+}
@@ -0,0 +1,37 @@
+pub mod disk_map;
+
+use aoc_common::solution::{Answer, Error, Solution};
+
+pub use disk_map::{CompactionStrategy, DiskMap, DiskMapError};
+
+/// Solver for Day 9: Disk Fragmenter
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+
+    type Input = DiskMap;
+
+    fn parse(input: &str) -> Result<DiskMap, Error> {
+        Ok(DiskMap::parse(input)?)
+    }
+
+    fn part1(disk: &DiskMap) -> Result<Answer, Error> {
+        let checksum = compact(disk, CompactionStrategy::Blocks).ok_or("the checksum overflows a u64")?;
+        Ok(checksum.into())
+    }
+
+    fn part2(disk: &DiskMap) -> Result<Answer, Error> {
+        let checksum = compact(disk, CompactionStrategy::WholeFiles).ok_or("the checksum overflows a u64")?;
+        Ok(checksum.into())
+    }
+}
+
+/// Compacts a copy of the disk with the given strategy and returns its checksum,
+/// or `None` if the checksum overflows a `u64`
+pub fn compact(disk: &DiskMap, strategy: CompactionStrategy) -> Option<u64> {
+    let mut disk = disk.clone();
+    disk.compact(strategy);
+    disk.checksum()
+}